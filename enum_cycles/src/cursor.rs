@@ -0,0 +1,38 @@
+use crate::EnumState;
+
+/// A stateful cursor over the values of an enum, which can easily be stored
+/// in a struct and driven from event handlers. Each call to `next()` advances
+/// the wrapped state and yields a copy of it, wrapping around indefinitely,
+/// even for enums derived with `#[clamp]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cursor<E: EnumState> {
+    state: E
+}
+
+impl<E: EnumState> Cursor<E> {
+    /// Constructs a new cursor beginning at the given state. The first call
+    /// to `next()` will yield the state after it.
+    pub fn new(state: E) -> Self {
+        Self { state }
+    }
+
+    /// Yields the value which `next()` would produce, without advancing.
+    #[must_use]
+    pub fn peek(&self) -> E {
+        self.state.wrapping_add(1)
+    }
+
+    /// Consumes the cursor, yielding its current state.
+    pub fn into_inner(self) -> E {
+        self.state
+    }
+}
+
+impl<E: EnumState> Iterator for Cursor<E> {
+    type Item = E;
+
+    fn next(&mut self) -> Option<E> {
+        self.state = self.state.wrapping_add(1);
+        Some(self.state.clone())
+    }
+}
@@ -0,0 +1,20 @@
+use core::fmt;
+
+/// The error returned by `EnumState::index_checked` when no state has the
+/// given index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct IndexOutOfRange {
+    /// The index which was requested.
+    pub index: usize,
+    /// The number of states in the enum. Note that valid indices are not
+    /// necessarily below this, e.g. under `#[by_discriminant]`.
+    pub size: usize
+}
+
+impl fmt::Display for IndexOutOfRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} is not a valid index", self.index)
+    }
+}
+
+impl core::error::Error for IndexOutOfRange {}
@@ -0,0 +1,18 @@
+use crate::EnumState;
+
+/// Provides owned copies of the data exposed by `EnumState`, for cases where
+/// a `Vec` is needed rather than a slice, e.g. for sorting. This is
+/// implemented for every type which implements `EnumState`.
+pub trait EnumStateExt: EnumState {
+    /// Yields an owned copy of every default value in the enum.
+    fn to_vec() -> Vec<Self> {
+        Self::values().to_vec()
+    }
+
+    /// Yields an owned copy of every name in the enum.
+    fn names_vec() -> Vec<String> {
+        Self::names().iter().map(|n| n.to_string()).collect()
+    }
+}
+
+impl<E: EnumState> EnumStateExt for E {}
@@ -0,0 +1,74 @@
+use crate::EnumState;
+
+/// A state which records each change made to it, so that those changes can
+/// be undone and redone, e.g. by an editor navigating between modes. Only
+/// the most recent `limit` states are retained.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StateHistory<E: EnumState> {
+    states: Vec<E>,
+    position: usize,
+    limit: usize
+}
+
+impl<E: EnumState> StateHistory<E> {
+    /// Constructs a new history beginning at the given state, retaining any
+    /// number of changes.
+    pub fn new(state: E) -> Self {
+        Self::with_limit(state, usize::MAX)
+    }
+
+    /// Constructs a new history beginning at the given state, retaining at
+    /// most `limit` states, including the current state. The oldest states
+    /// are discarded first.
+    pub fn with_limit(state: E, limit: usize) -> Self {
+        Self { states: vec![state], position: 0, limit: limit.max(1) }
+    }
+
+    /// Yields the current state.
+    #[must_use]
+    pub fn current(&self) -> &E {
+        &self.states[self.position]
+    }
+
+    /// Records a change to the given state, discarding any states which
+    /// could previously have been redone.
+    pub fn set(&mut self, state: E) {
+        self.states.truncate(self.position + 1);
+        self.states.push(state);
+        if self.states.len() > self.limit {
+            self.states.remove(0);
+        }
+        self.position = self.states.len() - 1;
+    }
+
+    /// Records a change to the state after the current state, as yielded
+    /// by `next()`.
+    pub fn advance(&mut self) {
+        self.set(self.current().peek_next());
+    }
+
+    /// Reverts to the state before the most recent change, returning whether
+    /// there was any such state.
+    pub fn undo(&mut self) -> bool {
+        if self.position == 0 {
+            return false;
+        }
+        self.position -= 1;
+        true
+    }
+
+    /// Reapplies the most recently undone change, returning whether there
+    /// was any such change.
+    pub fn redo(&mut self) -> bool {
+        if self.position + 1 >= self.states.len() {
+            return false;
+        }
+        self.position += 1;
+        true
+    }
+
+    /// Consumes the history, yielding its current state.
+    pub fn into_inner(mut self) -> E {
+        self.states.swap_remove(self.position)
+    }
+}
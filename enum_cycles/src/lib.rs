@@ -1,3 +1,18 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+mod cursor;
+mod error;
+#[cfg(feature = "std")]
+mod ext;
+#[cfg(feature = "std")]
+mod history;
+
+pub use cursor::Cursor;
+pub use error::IndexOutOfRange;
+#[cfg(feature = "std")]
+pub use ext::EnumStateExt;
+#[cfg(feature = "std")]
+pub use history::StateHistory;
 
 /// This trait defines all of the necessary procedures which enable enum values
 /// to behave as states of a single type. These types can be nested, for example,
@@ -55,6 +70,12 @@ pub trait EnumState: Sized + Clone + 'static {
     /// The number of elements in the enum.
     const _SIZE: usize;
 
+    /// Stores the index and name of each variant in the enum.
+    const _PAIRS: &'static [(usize, &'static str)];
+
+    /// Stores the index of each variant in the enum.
+    const _INDICES: &'static [usize];
+
     /// Skips the current state forward one value.
     fn next(&mut self) {
         self.skip(1);
@@ -65,50 +86,416 @@ pub trait EnumState: Sized + Clone + 'static {
         self.skip_backward(1);
     }
 
-    /// Increments the state by the input `num`. If the resulting
-    /// index would be greater than the maximum possible, this
-    /// function skips to the last possible state. If the current
-    /// state *is* the last possible state, it will skip to the
-    /// first possible state.
-    fn skip(&mut self, num: usize) {
-        let mut index = self.index();
+    /// Performs a `next()`, returning whether it wrapped around from the
+    /// last state to the first, e.g. to detect the completion of a cycle.
+    fn try_next(&mut self) -> bool {
+        let last = self.ordinal() == Self::size() - 1 && !self.is_leading_skip();
+        self.next();
+        last && self.ordinal() == 0
+    }
+
+    /// Performs a `previous()`, returning whether it wrapped around from
+    /// the first state to the last.
+    fn try_previous(&mut self) -> bool {
+        let first = self.ordinal() == 0;
+        self.previous();
+        first && self.ordinal() == Self::size() - 1
+    }
+
+    /// Moves to the next value unless the current state is already the
+    /// last, returning whether it moved. Unlike `next()`, this never wraps.
+    fn advance(&mut self) -> bool {
+        let next = if self.is_leading_skip() { 0 } else { self.ordinal() + 1 };
+        match Self::from_ordinal(next) {
+            Some(next) => {
+                *self = next;
+                true
+            }
+            None => false
+        }
+    }
+
+    /// Moves to the previous value unless the current state is already the
+    /// first, returning whether it moved. Unlike `previous()`, this never
+    /// wraps.
+    fn retreat(&mut self) -> bool {
+        match self.ordinal().checked_sub(1) {
+            Some(ordinal) => {
+                *self = Self::from_ordinal(ordinal).unwrap();
+                true
+            }
+            None => false
+        }
+    }
+
+    /// Yields a uniformly random state other than the current state. When
+    /// the enum has only one state, this is simply the current state.
+    #[cfg(feature = "rand")]
+    #[must_use]
+    fn random_different<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> Self {
         let size = Self::size();
-        let max = size - 1;
-        let sum = index + num;
+        if size < 2 {
+            return self.clone();
+        }
+        let ordinal = self.ordinal();
+        let i = rng.gen_range(0..size - 1);
+        Self::from_ordinal(if i >= ordinal { i + 1 } else { i }).unwrap()
+    }
+
+    /// Flips between the two states of a boolean-like enum. This always
+    /// wraps around, even for enums derived with `#[clamp]`, so on enums
+    /// with more than two variants it simply advances by one.
+    fn toggle(&mut self) {
+        *self = self.wrapping_add(1);
+    }
+
+    /// Bounces between two specific states, regardless of where they are
+    /// declared: the state becomes `b` if it is currently `a`, or else `a`.
+    /// States are compared by ordinal, so any nested values are ignored.
+    fn cycle_between(&mut self, a: &Self, b: &Self) {
+        *self = if self.ordinal() == a.ordinal() { b.clone() } else { a.clone() };
+    }
+
+    /// Yields the value which `next()` would skip to, without
+    /// modifying the current state.
+    #[must_use]
+    fn peek_next(&self) -> Self {
+        let mut next = self.clone();
+        next.next();
+        next
+    }
+
+    /// Yields the value which `previous()` would skip to, without
+    /// modifying the current state.
+    #[must_use]
+    fn peek_previous(&self) -> Self {
+        let mut previous = self.clone();
+        previous.previous();
+        previous
+    }
 
-        if index == max {
-            index = 0;
-        } else if sum > max {
-            index = max;
+    /// Skips forward one value as though every nested state were
+    /// flattened into a single list. Any nested states are cycled
+    /// through first, and only once they wrap around will the state
+    /// itself skip to the next variant, starting from the first value
+    /// of *its* nested states.
+    fn deep_next(&mut self) {
+        self.deep_step(true);
+    }
+
+    /// Skips backward one value as though every nested state were
+    /// flattened into a single list. This is the inverse of
+    /// `deep_next()`.
+    fn deep_previous(&mut self) {
+        self.deep_step(false);
+    }
+
+    /// Performs a single step of `deep_next()` when `forward` is `true`,
+    /// or `deep_previous()` otherwise, returning whether the state wrapped
+    /// around from one end of the flattened list to the other.
+    fn deep_step(&mut self, forward: bool) -> bool {
+        if !self.step_inner(forward) {
+            return false;
+        }
+        let size = Self::size();
+        let ordinal = self.ordinal();
+        let (target, wrapped) = if forward {
+            ((ordinal + 1) % size, ordinal + 1 == size)
         } else {
-            index = sum % size;
+            ((ordinal + size - 1) % size, ordinal == 0)
+        };
+        *self = Self::deep_from_ordinal(target, forward).unwrap();
+        wrapped
+    }
+
+    /// Steps any nested states held by the current variant, returning
+    /// whether they all wrapped around. Variants without nested states
+    /// always wrap, as there is nothing for them to step through.
+    fn step_inner(&mut self, _forward: bool) -> bool {
+        true
+    }
+
+    /// Yields the `n` values following the current state, in order,
+    /// wrapping around from the last state to the first as many times
+    /// as necessary.
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn next_n(&self, n: usize) -> Vec<Self> {
+        let size = Self::size();
+        let ordinal = self.ordinal();
+        (1..=n)
+            .map(|i| Self::from_ordinal((ordinal + i % size) % size).unwrap())
+            .collect()
+    }
+
+    /// Yields the `n` values preceding the current state, nearest first,
+    /// wrapping around from the first state to the last as many times
+    /// as necessary.
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn previous_n(&self, n: usize) -> Vec<Self> {
+        let size = Self::size();
+        let ordinal = self.ordinal();
+        (1..=n)
+            .map(|i| Self::from_ordinal((ordinal + size - i % size) % size).unwrap())
+            .collect()
+    }
+
+    /// Yields the `2 * radius + 1` values centered on the current state,
+    /// i.e. the `radius` values preceding it, itself, and the `radius`
+    /// values following it. When the window is larger than the enum,
+    /// values simply repeat in cycle order.
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn window(&self, radius: usize) -> Vec<Self> {
+        let mut window = self.previous_n(radius);
+        window.reverse();
+        window.push(self.clone());
+        window.extend(self.next_n(radius));
+        window
+    }
+
+    /// Increments the state by the input `num`, wrapping around from
+    /// the last possible state to the first as many times as necessary.
+    /// Enums derived with `#[clamp]` stop at the last state instead. A
+    /// `num` of zero leaves the state untouched, even for skipped variants.
+    fn skip(&mut self, num: usize) {
+        if num == 0 {
+            return;
         }
-        *self = Self::from_index(index).unwrap();
+        let size = Self::size();
+        let index = (self.ordinal() + num % size) % size;
+        *self = Self::from_ordinal(index).unwrap();
     }
 
-    /// Decrements the state by the input `num`. If the resulting
-    /// index would be less than zero, this function skips to the
-    /// first possible state. If the current state *is* the first
-    /// possible state, it will skip to the last possible state.
+    /// Decrements the state by the input `num`, wrapping around from
+    /// the first possible state to the last as many times as necessary.
+    /// Enums derived with `#[clamp]` stop at the first state instead. A
+    /// `num` of zero leaves the state untouched, even for skipped variants.
     fn skip_backward(&mut self, num: usize) {
-        let mut index = self.index();
+        if num == 0 {
+            return;
+        }
         let size = Self::size();
+        let index = (self.ordinal() + size - num % size) % size;
+        *self = Self::from_ordinal(index).unwrap();
+    }
 
+    /// Advances the state `count` times by `step` values each, wrapping
+    /// around from the last possible state to the first as necessary.
+    /// Unlike `skip(step * count)`, this cannot overflow.
+    fn step_by(&mut self, step: usize, count: usize) {
+        if step == 0 || count == 0 {
+            return;
+        }
+        let size = Self::size();
+        let offset = (step % size) * (count % size) % size;
+        let index = (self.ordinal() + offset) % size;
+        *self = Self::from_ordinal(index).unwrap();
+    }
+
+    /// Yields the state `n` values after the current state, wrapping around
+    /// from the last possible state to the first as many times as necessary.
+    /// Unlike `skip`, this does not modify the current state, and always
+    /// wraps, even for enums derived with `#[clamp]`. An `n` of zero yields
+    /// the current state, even for skipped variants.
+    #[must_use]
+    fn wrapping_add(&self, n: usize) -> Self {
+        if n == 0 {
+            return self.clone();
+        }
+        let size = Self::size();
+        Self::from_ordinal((self.ordinal() + n % size) % size).unwrap()
+    }
+
+    /// Yields the state `n` values before the current state, wrapping around
+    /// from the first possible state to the last as many times as necessary.
+    /// Unlike `skip_backward`, this does not modify the current state, and
+    /// always wraps, even for enums derived with `#[clamp]`. An `n` of zero
+    /// yields the current state, even for skipped variants.
+    #[must_use]
+    fn wrapping_sub(&self, n: usize) -> Self {
+        if n == 0 {
+            return self.clone();
+        }
+        let size = Self::size();
+        Self::from_ordinal((self.ordinal() + size - n % size) % size).unwrap()
+    }
+
+    /// Yields the state `delta` values away from the current state, moving
+    /// backward when `delta` is negative and always wrapping around at
+    /// either end. Derived implementations construct the resulting value
+    /// directly instead of cloning it out of `values()`. A `delta` of zero
+    /// yields the current state, even for skipped variants.
+    #[must_use]
+    fn offset(&self, delta: isize) -> Self {
+        if delta == 0 {
+            return self.clone();
+        }
+        Self::from_ordinal(self.ordinal_wrapping_offset(delta)).unwrap()
+    }
+
+    /// Determines the ordinal of the state which `offset(delta)` would
+    /// yield, without constructing it, e.g. to index an external array laid
+    /// out in the order of `values()`. Unlike `map_index`, which is keyed by
+    /// `index()`, this differs from the index under `#[by_discriminant]`.
+    #[must_use]
+    fn ordinal_wrapping_offset(&self, delta: isize) -> usize {
+        let size = Self::size();
+        let shift = delta.rem_euclid(size as isize) as usize;
+        (self.ordinal() + shift) % size
+    }
+
+    /// Determines the index of the state which `offset(delta)` would yield,
+    /// without constructing it, e.g. to index a table keyed like `map_index`.
+    /// This looks up the wrapped ordinal in `indices()`, so it remains correct
+    /// under `#[by_discriminant]` and `#[skip]`. A `delta` of zero yields the
+    /// current index, even for skipped variants.
+    #[must_use]
+    fn index_wrapping_offset(&self, delta: isize) -> usize {
+        if delta == 0 {
+            return self.index();
+        }
+        Self::_INDICES[self.ordinal_wrapping_offset(delta)]
+    }
+
+    /// Increments the state by the input `num`, stopping at the last
+    /// possible state instead of wrapping around to the first.
+    fn saturating_skip(&mut self, num: usize) {
         if num == 0 {
             return;
-        } else if index > num - 1 {
-            index -= num;
-        } else if index == 0 {
-            index = size - 1;
+        }
+        let max = Self::size() - 1;
+        let index = self.ordinal().saturating_add(num).min(max);
+        *self = Self::from_ordinal(index).unwrap();
+    }
+
+    /// Decrements the state by the input `num`, stopping at the first
+    /// possible state instead of wrapping around to the last.
+    fn saturating_skip_backward(&mut self, num: usize) {
+        if num == 0 {
+            return;
+        }
+        let index = self.ordinal().saturating_sub(num);
+        *self = Self::from_ordinal(index).unwrap();
+    }
+
+    /// Performs a `saturating_skip`, returning whether it stopped short at
+    /// the last possible state, i.e. it would otherwise have passed it.
+    fn skip_reporting(&mut self, num: usize) -> bool {
+        let clamped = self.ordinal().saturating_add(num) > Self::size() - 1;
+        self.saturating_skip(num);
+        clamped
+    }
+
+    /// Performs a `saturating_skip_backward`, returning whether it stopped
+    /// short at the first possible state, i.e. it would otherwise have
+    /// passed it.
+    fn skip_backward_reporting(&mut self, num: usize) -> bool {
+        let clamped = num > self.ordinal();
+        self.saturating_skip_backward(num);
+        clamped
+    }
+
+    /// Yields the state `num` values after the current state, or `None`
+    /// if doing so would pass the last possible state. Unlike `skip`,
+    /// this function does not modify the current state.
+    #[must_use]
+    fn checked_skip(&self, num: usize) -> Option<Self> {
+        if num == 0 {
+            return Some(self.clone());
+        }
+        Self::from_ordinal(self.ordinal().checked_add(num)?)
+    }
+
+    /// Yields the state `num` values before the current state, or `None`
+    /// if doing so would pass the first possible state. Unlike
+    /// `skip_backward`, this function does not modify the current state.
+    #[must_use]
+    fn checked_skip_backward(&self, num: usize) -> Option<Self> {
+        if num == 0 {
+            return Some(self.clone());
+        }
+        Self::from_ordinal(self.ordinal().checked_sub(num)?)
+    }
+
+    /// Moves the state by `delta` within its block of `modulus` values,
+    /// wrapping around inside of that block. Blocks begin at each multiple
+    /// of `modulus`, which means the final block may be shorter than the
+    /// rest when `modulus` does not evenly divide the size of the enum.
+    fn step_mod(&mut self, delta: isize, modulus: usize) {
+        debug_assert!(modulus > 0 && modulus <= Self::size());
+        if delta == 0 {
+            return;
+        }
+        let index = self.ordinal();
+        let base = index / modulus * modulus;
+        let len = modulus.min(Self::size() - base) as isize;
+        let offset = ((index - base) as isize + delta % len).rem_euclid(len);
+        *self = Self::from_ordinal(base + offset as usize).unwrap();
+    }
+
+    /// Moves the state to the nearest end of the range `[lo, hi]` of
+    /// ordinals if it falls outside of that range. The range itself
+    /// is first clamped to the bounds of the enum, with `lo` never
+    /// exceeding `hi`.
+    fn clamp_to_range(&mut self, lo: usize, hi: usize) {
+        let hi = hi.min(Self::size() - 1);
+        let lo = lo.min(hi);
+        let index = self.ordinal().clamp(lo, hi);
+        *self = Self::from_ordinal(index).unwrap();
+    }
+
+    /// Increments the state by the input `num`, cycling only through the
+    /// range `[lo, hi]` of ordinals and wrapping from `hi` back to `lo`.
+    /// This does nothing if the range is invalid or the current state
+    /// falls outside of it.
+    fn skip_in_range(&mut self, num: usize, lo: usize, hi: usize) {
+        let ordinal = self.ordinal();
+        if num == 0 || lo > hi || hi >= Self::size() || ordinal < lo || ordinal > hi {
+            return;
+        }
+        let len = hi - lo + 1;
+        let index = lo + (ordinal - lo + num % len) % len;
+        *self = Self::from_ordinal(index).unwrap();
+    }
+
+    /// Sets the state to the value at ordinal `i`, clamping `i` to the last
+    /// state when it is out of range.
+    fn saturating_set_index(&mut self, i: usize) {
+        *self = Self::from_ordinal(i.min(Self::size() - 1)).unwrap();
+    }
+
+    /// Sets the state to the value at ordinal `i`, wrapping around from the
+    /// last state to the first as many times as necessary.
+    fn wrapping_set_index(&mut self, i: usize) {
+        *self = Self::from_ordinal(i % Self::size()).unwrap();
+    }
+
+    /// Sets the state to `other`, provided its ordinal is within the bounds
+    /// of the enum, returning whether it did. This is always the case for
+    /// values constructed normally, but provides a guard point for values
+    /// received over FFI.
+    fn assign_checked(&mut self, other: Self) -> bool {
+        if other.ordinal() < Self::size() {
+            *self = other;
+            true
         } else {
-            index = 0;
+            false
         }
-        *self = Self::from_index(index).unwrap();
     }
 
     /// Attempts to retrieve the default value for the variant
     /// at the given index.
+    #[must_use]
     fn from_index(i: usize) -> Option<Self> {
+        Self::from_ordinal(i)
+    }
+
+    /// Attempts to retrieve the default value for the variant
+    /// at the given ordinal, i.e. its position in `values()`.
+    #[must_use]
+    fn from_ordinal(i: usize) -> Option<Self> {
         if i < Self::_SIZE {
             Some(Self::_VALUES[i].clone())
         } else {
@@ -116,39 +503,379 @@ pub trait EnumState: Sized + Clone + 'static {
         }
     }
 
+    /// Attempts to retrieve the default value for the variant
+    /// with the given name.
+    #[must_use]
+    fn from_name(name: &str) -> Option<Self> {
+        Self::_NAMES.iter()
+            .position(|n| *n == name)
+            .and_then(Self::from_ordinal)
+    }
+
+    /// Attempts to retrieve the default value for the variant at the given
+    /// index, producing an error which describes the problem otherwise. This
+    /// is convenient for propagating errors with `?`.
+    fn index_checked(i: usize) -> Result<Self, IndexOutOfRange> {
+        Self::from_index(i).ok_or(IndexOutOfRange { index: i, size: Self::size() })
+    }
+
+    /// Equivalent to `from_index`, reading more naturally when constructing
+    /// a state in expression position.
+    #[must_use]
+    fn with_index(i: usize) -> Option<Self> {
+        Self::from_index(i)
+    }
+
+    /// Equivalent to `from_name`, reading more naturally when constructing
+    /// a state in expression position.
+    #[must_use]
+    fn with_name(name: &str) -> Option<Self> {
+        Self::from_name(name)
+    }
+
+    /// Determines whether any variant has the given name.
+    #[must_use]
+    fn contains_name(name: &str) -> bool {
+        Self::_NAMES.contains(&name)
+    }
+
+    /// Determines the index of the variant with the given name, i.e. the
+    /// value which `index()` would yield for that variant. Unlike
+    /// `from_name`, this never constructs a value.
+    #[must_use]
+    fn index_of_name(name: &str) -> Option<usize> {
+        Self::_PAIRS.iter()
+            .find(|(_, n)| *n == name)
+            .map(|(i, _)| *i)
+    }
+
+    /// Retrieves the first value in `values()` which satisfies the given
+    /// predicate, if any.
+    #[must_use]
+    fn find_first<F: FnMut(&Self) -> bool>(mut pred: F) -> Option<Self> {
+        Self::values().iter().find(|s| pred(s)).cloned()
+    }
+
+    /// Determines the position in `values()`, i.e. the ordinal, of the first
+    /// value which satisfies the given predicate, if any.
+    #[must_use]
+    fn find_index<F: FnMut(&Self) -> bool>(pred: F) -> Option<usize> {
+        Self::values().iter().position(pred)
+    }
+
+    /// Attempts to retrieve the variant at the given ordinal with each
+    /// of its nested states set to their first deep value when `forward`
+    /// is `true`, or their last deep value otherwise.
+    #[must_use]
+    fn deep_from_ordinal(i: usize, _forward: bool) -> Option<Self> {
+        Self::from_ordinal(i)
+    }
+
+    /// Yields the total number of values in the flattened order of
+    /// `deep_next()`. Each variant contributes the product of the totals
+    /// of its nested states, or 1 if it has none.
+    #[must_use]
+    fn total_states() -> usize {
+        Self::size()
+    }
+
+    /// Yields every value in the flattened order of `deep_next()`,
+    /// beginning with `deep_first()`.
+    #[cfg(feature = "std")]
+    #[must_use]
+    fn all_deep_states() -> Vec<Self> {
+        let total = Self::total_states();
+        let mut states = Vec::with_capacity(total);
+        let mut state = Self::deep_first();
+        for _ in 0..total {
+            states.push(state.clone());
+            state.deep_next();
+        }
+        states
+    }
+
+    /// Yields the first value in the flattened order of `deep_next()`.
+    #[must_use]
+    fn deep_first() -> Self {
+        Self::deep_from_ordinal(0, true).unwrap()
+    }
+
+    /// Yields the last value in the flattened order of `deep_next()`.
+    #[must_use]
+    fn deep_last() -> Self {
+        Self::deep_from_ordinal(Self::size() - 1, false).unwrap()
+    }
+
     /// Yields the set of possible names for this enum.
+    #[must_use]
     fn names() -> &'static [&'static str] {
         Self::_NAMES
     }
 
+    /// Appends every name in this enum to `buf`, separated by `sep`. This
+    /// is equivalent to `names().join(sep)`, but reuses the allocation of
+    /// an existing buffer, e.g. when rebuilding a label on every frame.
+    #[cfg(feature = "std")]
+    fn write_names(buf: &mut String, sep: &str) {
+        for (i, name) in Self::names().iter().enumerate() {
+            if i > 0 {
+                buf.push_str(sep);
+            }
+            buf.push_str(name);
+        }
+    }
+
     /// Yields the set of default values for this enum.
+    #[must_use]
     fn values() -> &'static [Self] {
         Self::_VALUES
     }
 
+    /// Yields the names within the given range of ordinals. Either end of
+    /// the range is clamped to `size()`, yielding an empty slice when the
+    /// range falls entirely outside of the enum.
+    #[must_use]
+    fn names_range(range: core::ops::Range<usize>) -> &'static [&'static str] {
+        let end = range.end.min(Self::size());
+        &Self::_NAMES[range.start.min(end)..end]
+    }
+
+    /// Yields the default values within the given range of ordinals. Either
+    /// end of the range is clamped to `size()`, yielding an empty slice when
+    /// the range falls entirely outside of the enum.
+    #[must_use]
+    fn values_range(range: core::ops::Range<usize>) -> &'static [Self] {
+        let end = range.end.min(Self::size());
+        &Self::_VALUES[range.start.min(end)..end]
+    }
+
+    /// Yields the index of each variant in this enum, in the same order as
+    /// `values()`. These are only contiguous when every variant is cycled
+    /// through in declaration order.
+    #[must_use]
+    fn indices() -> &'static [usize] {
+        Self::_INDICES
+    }
+
+    /// Yields the index and name of each variant in this enum.
+    #[must_use]
+    fn index_name_pairs() -> &'static [(usize, &'static str)] {
+        Self::_PAIRS
+    }
+
     /// Yields the default value for this enum.
     fn default() -> Self {
         Self::_DEFAULT
     }
 
     /// Yields the first value in the enum.
+    #[must_use]
     fn first() -> Self {
         Self::_FIRST
     }
 
     /// Yields the last value in the enum.
+    #[must_use]
     fn last() -> Self {
         Self::_LAST
     }
 
+    /// Yields the first and last values in the enum, e.g. for building an
+    /// inclusive range.
+    #[must_use]
+    fn bounds() -> (Self, Self) {
+        (Self::first(), Self::last())
+    }
+
     /// Yields the number of elements in the enum.
+    #[must_use]
     fn size() -> usize {
         Self::_SIZE
     }
 
-    /// Determines the index of the current state.
+    /// Yields the number of variants declared in the enum. This is equal
+    /// to `size()` unless some variants are marked with `#[skip]`, which
+    /// are counted here but cannot be cycled through.
+    #[must_use]
+    fn declared_count() -> usize {
+        Self::size()
+    }
+
+    /// Determines whether the current state is a variant marked with
+    /// `#[skip]` which is declared before every other variant. Such a state
+    /// shares the ordinal of the last value, but moving forward from it
+    /// reaches the first value without wrapping.
+    fn is_leading_skip(&self) -> bool {
+        false
+    }
+
+    /// Yields the name of every state in cycle order, beginning with the
+    /// current state and wrapping around to those before it.
+    #[must_use]
+    fn cycle_names(&self) -> impl Iterator<Item = &'static str> {
+        let start = self.ordinal();
+        let size = Self::size();
+        (0..size).map(move |i| Self::_NAMES[(start + i) % size])
+    }
+
+    /// Yields an endless sequence of states, beginning with the current
+    /// state and advancing as `next()` would. Combine with `take(n)` to
+    /// produce a bounded run.
+    #[must_use]
+    fn cycle_forever(&self) -> impl Iterator<Item = Self> {
+        core::iter::successors(Some(self.clone()), |s| Some(s.peek_next()))
+    }
+
+    /// Determines whether the current state is the same variant as the
+    /// enum's default. States are compared by index, so any nested values
+    /// are ignored.
+    #[must_use]
+    fn is_default(&self) -> bool {
+        self.index() == Self::_DEFAULT.index()
+    }
+
+    /// Determines the position of the current state counting back from the
+    /// last state, e.g. for numbering states in right-to-left layouts.
+    #[must_use]
+    fn reverse_index(&self) -> usize {
+        Self::size() - 1 - self.ordinal()
+    }
+
+    /// Determines the number of times `next()` would have to be called for
+    /// this state to reach `other`, wrapping around from the last state to
+    /// the first.
+    #[must_use]
+    fn distance_to(&self, other: &Self) -> usize {
+        let size = Self::size();
+        (other.ordinal() + size - self.ordinal()) % size
+    }
+
+    /// Determines the shortest number of steps from this state to `other`,
+    /// wrapping around at either end. This is positive when the shortest
+    /// route is forward and negative when it is backward. When both routes
+    /// are equally long, the forward route is chosen.
+    #[must_use]
+    fn shortest_signed_distance(&self, other: &Self) -> isize {
+        let forward = self.distance_to(other);
+        let backward = other.distance_to(self);
+        if forward <= backward {
+            forward as isize
+        } else {
+            -(backward as isize)
+        }
+    }
+
+    /// Moves the state one step toward `target` along the shortest route,
+    /// as given by `shortest_signed_distance`, doing nothing once it has
+    /// been reached. Like `offset`, this always wraps around at either end.
+    fn cycle_to(&mut self, target: &Self) {
+        let step = self.shortest_signed_distance(target).signum();
+        if step != 0 {
+            *self = self.offset(step);
+        }
+    }
+
+    /// Determines whether `other` is at most `tolerance` states away from
+    /// this state in either direction, wrapping around at either end. As no
+    /// two states are more than `size() / 2` apart, any tolerance at least
+    /// that large, including one of `size()` or more, holds for every pair.
+    #[must_use]
+    fn within(&self, other: &Self, tolerance: usize) -> bool {
+        self.distance_to(other).min(other.distance_to(self)) <= tolerance
+    }
+
+    /// Determines the number of states between this state and `other`,
+    /// in either direction and without wrapping around. This suits enums
+    /// which are modeled as a line rather than a circle, e.g. those derived
+    /// with `#[clamp]`.
+    #[must_use]
+    fn linear_distance(&self, other: &Self) -> usize {
+        let (a, b) = (self.ordinal(), other.ordinal());
+        a.max(b) - a.min(b)
+    }
+
+    /// Determines the number of states from `a` to `b` in forward order,
+    /// including both ends and wrapping around if `b` precedes `a`. This is
+    /// always one greater than `a.distance_to(b)`.
+    #[must_use]
+    fn count_between(a: &Self, b: &Self) -> usize {
+        a.distance_to(b) + 1
+    }
+
+    /// Summarizes a transition from this state to `other`, yielding the
+    /// name of `other` and the forward distance between the two.
+    #[must_use]
+    fn difference_name(&self, other: &Self) -> (&'static str, usize) {
+        (other.name(), self.distance_to(other))
+    }
+
+    /// Determines the `(row, column)` of the current state when each
+    /// state is laid out in rows of `cols` values.
+    #[must_use]
+    fn grid_position(&self, cols: usize) -> (usize, usize) {
+        debug_assert!(cols > 0);
+        let ordinal = self.ordinal();
+        (ordinal / cols, ordinal % cols)
+    }
+
+    /// Retrieves the entry for the current state from a table of values
+    /// which is indexed in parallel with the enum, or `None` if the table
+    /// is too short to contain one.
+    #[must_use]
+    fn map_index<'a, T>(&self, table: &'a [T]) -> Option<&'a T> {
+        table.get(self.index())
+    }
+
+    /// Determines how far through the enum the current state lies as a
+    /// fraction in `[0.0, 1.0]`, e.g. for use in progress bars. Enums
+    /// with a single variant always yield `0.0`.
+    #[must_use]
+    fn position(&self) -> f32 {
+        match Self::size() {
+            1 => 0.0,
+            size => self.ordinal() as f32 / (size - 1) as f32
+        }
+    }
+
+    /// Yields the state nearest to the given fraction of the way through
+    /// the enum. This is the inverse of `position()`. Fractions exactly
+    /// between two states round up to the later state, values outside of
+    /// `[0.0, 1.0]` are clamped, and `NaN` yields the default state.
+    #[must_use]
+    fn from_position(p: f32) -> Self {
+        if p.is_nan() {
+            return Self::default();
+        }
+        let max = (Self::size() - 1) as f32;
+        let index = (p.clamp(0.0, 1.0) * max + 0.5) as usize;
+        Self::from_ordinal(index).unwrap()
+    }
+
+    /// Determines the position of the current state in `values()`,
+    /// which is the basis for cycling between states. This is equal
+    /// to `index()` unless the enum is derived with an attribute such
+    /// as `#[by_discriminant]`.
+    fn ordinal(&self) -> usize {
+        self.index()
+    }
+
+    /// Determines the index of the current state. By default, this is
+    /// its position in declaration order.
     fn index(&self) -> usize;
 
     /// Determines the name of the current state.
     fn name(&self) -> &'static str;
-}
\ No newline at end of file
+
+    /// Retrieves the doc comments on the current state's variant, or an
+    /// empty string if it has none.
+    fn description(&self) -> &'static str {
+        ""
+    }
+}
+
+/// Applies `f` to a copy of every value in `E::values()`, in order.
+pub fn for_each<E: EnumState>(mut f: impl FnMut(E)) {
+    for value in E::values() {
+        f(value.clone());
+    }
+}
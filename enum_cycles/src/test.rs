@@ -1,13 +1,19 @@
+#![allow(clippy::explicit_counter_loop)]
+
 #[macro_use]
 extern crate enum_cycles_derive;
 
-use enum_cycles::EnumState;
+use std::collections::{BTreeMap, HashMap};
+use std::convert::TryFrom;
+use std::marker::PhantomData;
+
+use enum_cycles::{Cursor, EnumState, EnumStateExt, IndexOutOfRange, StateHistory};
 use Numbers::*;
 use Letters::*;
 use Outer::*;
 
-#[default(One)]
 #[derive(Debug, PartialEq, Clone, EnumState)]
+#[default(One)]
 enum Numbers {
     Zero,
     One,
@@ -28,8 +34,8 @@ enum Letters {
     C
 }
 
-#[auto]
 #[derive(Debug, PartialEq, Clone, EnumState)]
+#[auto]
 enum Outer {
     #[last]
     NumLast(Numbers),
@@ -39,6 +45,271 @@ enum Outer {
     LetAuto(Letters),
 }
 
+#[derive(Debug, PartialEq, Clone, EnumState)]
+enum Seasons {
+    Spring,
+    /// The warmest season.
+    #[default]
+    Summer,
+    /// The season in which leaves fall.
+    ///
+    /// Also known as autumn.
+    #[rename = "Fall"]
+    Autumn,
+    Winter
+}
+
+#[derive(Debug, PartialEq, Clone, Copy, EnumState)]
+#[copy]
+enum Compass {
+    North,
+    East,
+    South,
+    West
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+#[first]
+enum Overrides {
+    NumFirst(Numbers),
+    #[auto]
+    NumAuto(Numbers),
+    #[auto]
+    LetAuto(Letters),
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+#[by_discriminant]
+enum Sparse {
+    B = 7,
+    C,
+    A = 3,
+    D = 12
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+enum Pairs {
+    #[default(Three)]
+    Single(Numbers),
+    #[default(One, B)]
+    Listed(Numbers, Letters),
+    #[default((Two, C))]
+    Grouped(Numbers, Letters),
+    #[default((4, 5))]
+    Tuple((u8, u8))
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+#[wrap]
+enum Wrapping {
+    Low,
+    Mid,
+    High
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+#[clamp]
+enum Clamping {
+    Low,
+    Mid,
+    High
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+enum Steps {
+    Start,
+    #[skip]
+    Hidden(u8),
+    Middle,
+    End
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+enum Preamble {
+    #[skip]
+    Intro,
+    Verse,
+    Chorus
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+#[auto]
+enum Mixed {
+    Both(#[first] Numbers, #[last] Letters),
+    Partial(#[last] Numbers, Letters),
+    #[first]
+    Overridden(Numbers, #[auto] Numbers)
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+enum Tree {
+    Leaf,
+    #[default(&Tree::Leaf)]
+    Node(&'static Tree)
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+enum Forest {
+    Leaf,
+    #[skip]
+    Node(Box<Forest>)
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+#[allow(non_camel_case_types)]
+enum Keywords {
+    r#Match,
+    r#type
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+enum Single {
+    Only
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+enum Large {
+    V136, V126, V189, V051, V145, V098, V000, V068, V040, V004,
+    V083, V082, V112, V196, V053, V016, V008, V148, V180, V139,
+    V106, V061, V062, V084, V178, V014, V093, V193, V181, V103,
+    V057, V199, V113, V127, V195, V144, V197, V018, V033, V198,
+    V064, V072, V100, V159, V031, V034, V131, V162, V123, V043,
+    V038, V091, V047, V177, V135, V117, V151, V141, V179, V013,
+    V028, V176, V085, V154, V065, V173, V021, V096, V094, V046,
+    V153, V163, V183, V048, V032, V128, V114, V194, V024, V104,
+    V009, V150, V052, V019, V182, V109, V088, V002, V111, V175,
+    V036, V010, V089, V119, V190, V003, V058, V149, V156, V171,
+    V186, V030, V165, V152, V097, V158, V029, V090, V007, V107,
+    V012, V143, V125, V134, V124, V142, V075, V167, V055, V168,
+    V192, V035, V185, V080, V081, V025, V116, V147, V191, V050,
+    V037, V087, V120, V132, V157, V118, V138, V020, V011, V174,
+    V078, V059, V041, V115, V110, V073, V108, V105, V172, V130,
+    V170, V146, V169, V001, V076, V188, V166, V071, V121, V161,
+    V045, V063, V129, V099, V086, V155, V022, V095, V160, V187,
+    V079, V042, V049, V069, V066, V164, V006, V054, V067, V027,
+    V044, V070, V092, V137, V133, V056, V015, V074, V140, V102,
+    V005, V017, V023, V039, V122, V101, V184, V026, V077, V060
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+enum Dynamic {
+    #[default(<Letters as EnumState>::_LAST)]
+    Constant(Letters),
+    #[first]
+    #[default_expr(Letters::last())]
+    Runtime(Letters),
+    #[default { letter: Letters::A, n: 0 }]
+    #[default_expr { letter: Letters::from_name("B").unwrap(), n: Numbers::size() as u8 }]
+    Named { letter: Letters, n: u8 }
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+#[non_exhaustive]
+enum Open {
+    First,
+    Second
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+#[repr(u16)]
+enum Protocol {
+    Ping = 0x10,
+    Pong,
+    Data = 0x200,
+    Close
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+#[start_at(Two)]
+enum Rotated {
+    Zero,
+    One,
+    Two,
+    Three
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+enum Tagged {
+    Marker(PhantomData<u8>),
+    #[auto]
+    Mixed(Letters, PhantomData<u8>),
+    Named { #[first] letter: Letters, #[last] number: Numbers, marker: PhantomData<u8> }
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+#[default(Volume::Medium)]
+#[std_default]
+enum Volume {
+    Low,
+    Medium,
+    High
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+enum Switch {
+    On,
+    Off
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+#[clamp]
+enum Latch {
+    Open,
+    Shut
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, EnumState)]
+#[ord]
+enum Rank {
+    Bronze,
+    Silver,
+    Gold,
+    Platinum
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, EnumState)]
+#[hash]
+enum Suit {
+    Clubs,
+    Diamonds,
+    Hearts,
+    Spades
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+#[try_from]
+enum Signal {
+    Red,
+    Amber,
+    Green
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+#[repr_u8]
+enum Priority {
+    Low,
+    Normal,
+    Urgent
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+#[into_primitives]
+enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+#[as_ref_str]
+enum Planet {
+    Mercury,
+    Venus,
+    Earth
+}
+
 #[test]
 fn test_skip() {
     let count = 25;
@@ -58,10 +329,10 @@ fn test_skip() {
 
     let expected = vec![
         1, 2, 3, 4, 5, // +1
-        7, 9, 0, 2, 4, // +2
-        7, 9, 0, 3, 6, // +3
-        9, 0, 4, 8, 9, // +4
-        0, 5, 9, 0, 5, // +5
+        7, 9, 1, 3, 5, // +2
+        8, 1, 4, 7, 0, // +3
+        4, 8, 2, 6, 0, // +4
+        5, 0, 5, 0, 5, // +5
     ];
 
     assert_eq!(vals, expected);
@@ -79,6 +350,38 @@ fn test_skip_range() {
     }
 }
 
+#[test]
+fn test_skip_zero() {
+    for value in Numbers::values() {
+        let mut n = value.clone();
+        n.skip(0);
+        assert_eq!(n, *value);
+        n.skip_backward(0);
+        assert_eq!(n, *value);
+    }
+    let mut c = Clamping::High;
+    c.skip(0);
+    assert_eq!(c, Clamping::High);
+}
+
+#[test]
+fn test_step_by() {
+    for step in 0..25 {
+        for count in 0..5 {
+            let mut expected = Three;
+            for _ in 0..count {
+                expected.skip(step);
+            }
+            let mut n = Three;
+            n.step_by(step, count);
+            assert_eq!(n, expected);
+        }
+    }
+    let mut n = Three;
+    n.step_by(usize::MAX, usize::MAX);
+    assert_eq!(n, Eight);
+}
+
 #[test]
 fn test_skip_backward() {
     let count = 25;
@@ -98,10 +401,10 @@ fn test_skip_backward() {
 
     let expected = vec![
         8, 7, 6, 5, 4, // -1
-        2, 0, 9, 7, 5, // -2
-        2, 0, 9, 6, 3, // -3
-        0, 9, 5, 1, 0, // -4
-        9, 4, 0, 9, 4, // -5
+        2, 0, 8, 6, 4, // -2
+        1, 8, 5, 2, 9, // -3
+        5, 1, 7, 3, 9, // -4
+        4, 9, 4, 9, 4, // -5
     ];
 
     assert_eq!(vals, expected);
@@ -120,31 +423,1549 @@ fn test_skip_backward_range() {
 }
 
 #[test]
-fn test_properties() {
-    let names = [
-        "Zero", "One", "Two", "Three", "Four",
-        "Five", "Six", "Seven", "Eight", "Nine"
-    ];
-    let values = [
-        Zero, One, Two, Three, Four,
-        Five, Six, Seven, Eight, Nine
-    ];
+fn test_wrap() {
+    let mut w = Wrapping::High;
+    w.next();
+    assert_eq!(w, Wrapping::Low);
+    w.previous();
+    assert_eq!(w, Wrapping::High);
+    w.skip(4);
+    assert_eq!(w, Wrapping::Low);
+    w.skip_backward(5);
+    assert_eq!(w, Wrapping::Mid);
+}
 
-    assert_eq!(Numbers::names(), names);
-    assert_eq!(Numbers::values(), values);
-    assert_eq!(Numbers::first(), Zero);
-    assert_eq!(Numbers::last(), Nine);
-    assert_eq!(Numbers::size(), 10);
+#[test]
+fn test_clamp() {
+    let mut c = Clamping::High;
+    c.next();
+    assert_eq!(c, Clamping::High);
+    c.skip_backward(5);
+    assert_eq!(c, Clamping::Low);
+    c.previous();
+    assert_eq!(c, Clamping::Low);
+    c.skip(4);
+    assert_eq!(c, Clamping::High);
+
+    let mut l = Latch::Shut;
+    l.next();
+    assert_eq!(l, Latch::Shut);
+    l.toggle();
+    assert_eq!(l, Latch::Open);
 }
 
 #[test]
-fn test_defaults() {
-    let values = [
-        NumLast(Nine), // #[last] overrides #[auto]
-        LetManual(B),  // #[default(...)] overrides #[auto]
-        NumAuto(One),  // Default specified, used by #[auto]
-        LetAuto(A),    // No default => #[auto] uses first
-    ];
+fn test_wrapping_add() {
+    assert_eq!(Seven.wrapping_add(2), Nine);
+    assert_eq!(Seven.wrapping_add(3), Zero);
+    assert_eq!(Seven.wrapping_add(10), Seven);
+    assert_eq!(Seven.wrapping_add(25), Two);
+    assert_eq!(Clamping::High.wrapping_add(1), Clamping::Low);
+}
 
-    assert_eq!(Outer::values(), values);
-}
\ No newline at end of file
+#[test]
+fn test_wrapping_sub() {
+    assert_eq!(Two.wrapping_sub(2), Zero);
+    assert_eq!(Two.wrapping_sub(3), Nine);
+    assert_eq!(Two.wrapping_sub(10), Two);
+    assert_eq!(Two.wrapping_sub(25), Seven);
+    assert_eq!(Clamping::Low.wrapping_sub(1), Clamping::High);
+}
+
+#[test]
+fn test_offset() {
+    assert_eq!(Seven.offset(3), Zero);
+    assert_eq!(Two.offset(-3), Nine);
+    assert_eq!(Outer::NumLast(Nine).offset(1), Outer::values()[1]);
+
+    let mut n = Zero;
+    for i in 0..10_000 {
+        let delta = (i % 23) as isize - 11;
+        let expected = if delta < 0 {
+            n.wrapping_sub(delta.unsigned_abs())
+        } else {
+            n.wrapping_add(delta as usize)
+        };
+        n = n.offset(delta);
+        assert_eq!(n, expected);
+    }
+    assert_eq!(Zero.offset(isize::MIN), Two);
+}
+
+/// Compares the generated `offset` against stepping through `from_ordinal`.
+/// Run with `cargo test --release -- --ignored --nocapture bench_offset`.
+#[test]
+#[ignore]
+fn bench_offset() {
+    use std::hint::black_box;
+    use std::time::Instant;
+
+    const ITERATIONS: usize = 10_000_000;
+    let mut n = Zero;
+    let start = Instant::now();
+    for i in 0..ITERATIONS {
+        n = black_box(n.offset(black_box((i % 3) as isize)));
+    }
+    let table = start.elapsed();
+
+    let mut m = Zero;
+    let start = Instant::now();
+    for i in 0..ITERATIONS {
+        m = black_box(m.wrapping_add(black_box(i % 3)));
+    }
+    let stepping = start.elapsed();
+
+    assert_eq!(n, m);
+    println!("offset: {:?}, wrapping_add: {:?}", table, stepping);
+}
+
+#[test]
+fn test_saturating_skip() {
+    let mut n = Numbers::Zero;
+
+    n.saturating_skip(3);
+    assert_eq!(n, Three);
+    n.saturating_skip(1000);
+    assert_eq!(n, Nine);
+    n.saturating_skip(1);
+    assert_eq!(n, Nine);
+    n.saturating_skip(usize::MAX);
+    assert_eq!(n, Nine);
+}
+
+#[test]
+fn test_saturating_skip_backward() {
+    let mut n = Numbers::Nine;
+
+    n.saturating_skip_backward(3);
+    assert_eq!(n, Six);
+    n.saturating_skip_backward(1000);
+    assert_eq!(n, Zero);
+    n.saturating_skip_backward(1);
+    assert_eq!(n, Zero);
+}
+
+#[test]
+fn test_peek() {
+    assert_eq!(Zero.peek_next(), One);
+    assert_eq!(Zero.peek_previous(), Nine);
+    assert_eq!(Nine.peek_next(), Zero);
+}
+
+#[test]
+fn test_next_n() {
+    assert_eq!(Zero.next_n(3), [One, Two, Three]);
+    assert_eq!(Eight.next_n(3), [Nine, Zero, One]);
+    assert_eq!(Zero.next_n(0), []);
+    assert_eq!(A.next_n(7), [B, C, A, B, C, A, B]);
+}
+
+#[test]
+fn test_previous_n() {
+    assert_eq!(Three.previous_n(3), [Two, One, Zero]);
+    assert_eq!(One.previous_n(3), [Zero, Nine, Eight]);
+    assert_eq!(Zero.previous_n(0), []);
+    assert_eq!(A.previous_n(7), [C, B, A, C, B, A, C]);
+}
+
+#[test]
+fn test_cycle_forever() {
+    let indices: Vec<_> = Numbers::Zero.cycle_forever()
+        .take(25)
+        .map(|n| n.index())
+        .collect();
+    let expected: Vec<_> = (0..25).map(|i| i % 10).collect();
+
+    assert_eq!(indices, expected);
+    assert_eq!(Seven.cycle_forever().next(), Some(Seven));
+}
+
+#[test]
+fn test_skipped_variants() {
+    assert_eq!(Steps::declared_count(), Steps::size() + 1);
+    assert_eq!(Steps::names(), ["Start", "Middle", "End"]);
+    assert_eq!(Steps::Hidden(3).index(), 1);
+    assert_eq!(Steps::Hidden(3).name(), "Hidden");
+    assert_eq!(Steps::Middle.index(), 2);
+    assert_eq!(Steps::from_index(1), None);
+    assert_eq!(Steps::from_index(2), Some(Steps::Middle));
+    assert_eq!(Steps::Hidden(3).peek_next(), Steps::Middle);
+    assert_eq!(Steps::End.peek_next(), Steps::Start);
+    assert_eq!(Numbers::declared_count(), Numbers::size());
+}
+
+#[test]
+fn test_leading_skipped_variant() {
+    let mut p = Preamble::Intro;
+    assert!(p.is_leading_skip());
+    assert!(!Preamble::Chorus.is_leading_skip());
+    assert!(!Steps::Hidden(3).is_leading_skip());
+    assert!(!p.try_next());
+    assert_eq!(p, Preamble::Verse);
+
+    let mut p = Preamble::Intro;
+    assert!(p.advance());
+    assert_eq!(p, Preamble::Verse);
+
+    let mut p = Preamble::Chorus;
+    assert!(!p.advance());
+    assert!(p.try_next());
+    assert_eq!(p, Preamble::Verse);
+}
+
+#[test]
+fn test_skip_zero_on_skipped_variant() {
+    let mut s = Steps::Hidden(3);
+    s.skip(0);
+    assert_eq!(s, Steps::Hidden(3));
+    s.skip_backward(0);
+    assert_eq!(s, Steps::Hidden(3));
+    s.saturating_skip(0);
+    assert_eq!(s, Steps::Hidden(3));
+    s.step_by(2, 0);
+    assert_eq!(s, Steps::Hidden(3));
+    s.step_mod(0, 3);
+    assert_eq!(s, Steps::Hidden(3));
+    s.skip_in_range(0, 0, 2);
+    assert_eq!(s, Steps::Hidden(3));
+    s.skip(1);
+    assert_eq!(s, Steps::Middle);
+}
+
+#[test]
+fn test_zero_offset_on_skipped_variant() {
+    let s = Steps::Hidden(3);
+    assert_eq!(s.offset(0), s);
+    assert_eq!(s.wrapping_add(0), s);
+    assert_eq!(s.wrapping_sub(0), s);
+    assert_eq!(s.checked_skip(0), Some(s.clone()));
+    assert_eq!(s.checked_skip_backward(0), Some(s.clone()));
+    assert_eq!(s.offset(1), Steps::Middle);
+}
+
+#[test]
+fn test_field_attributes() {
+    assert_eq!(Mixed::values(), [
+        Mixed::Both(Zero, C),
+        Mixed::Partial(Nine, A),
+        Mixed::Overridden(Zero, One),
+    ]);
+
+    let mut m = Mixed::Both(Nine, C);
+    m.deep_next();
+    assert_eq!(m, Mixed::Partial(Zero, A));
+}
+
+#[test]
+fn test_recursive_default() {
+    assert_eq!(Tree::values(), [Tree::Leaf, Tree::Node(&Tree::Leaf)]);
+    assert_eq!(Tree::Node(&Tree::Leaf).peek_next(), Tree::Leaf);
+}
+
+#[test]
+fn test_recursive_skipped() {
+    assert_eq!(Forest::values(), [Forest::Leaf]);
+    assert_eq!(Forest::Node(Box::new(Forest::Leaf)).peek_next(), Forest::Leaf);
+}
+
+#[test]
+fn test_map_index() {
+    let colors = ["red", "green", "blue"];
+
+    assert_eq!(A.map_index(&colors), Some(&"red"));
+    assert_eq!(C.map_index(&colors), Some(&"blue"));
+    assert_eq!(C.map_index(&colors[..2]), None);
+}
+
+#[test]
+fn test_position() {
+    assert_eq!(Letters::A.position(), 0.0);
+    assert_eq!(Letters::B.position(), 0.5);
+    assert_eq!(Letters::C.position(), 1.0);
+    assert_eq!(Single::Only.position(), 0.0);
+}
+
+#[test]
+fn test_from_position() {
+    assert_eq!(Letters::from_position(0.0), A);
+    assert_eq!(Letters::from_position(0.25), B);
+    assert_eq!(Letters::from_position(0.5), B);
+    assert_eq!(Letters::from_position(1.0), C);
+    assert_eq!(Letters::from_position(-3.0), A);
+    assert_eq!(Letters::from_position(7.5), C);
+    assert_eq!(Numbers::from_position(f32::NAN), One);
+    assert_eq!(Single::from_position(0.8), Single::Only);
+}
+
+#[test]
+fn test_write_names() {
+    let mut buf = String::from("Seasons: ");
+    Seasons::write_names(&mut buf, ", ");
+
+    assert_eq!(buf, format!("Seasons: {}", Seasons::names().join(", ")));
+    buf.clear();
+    Letters::write_names(&mut buf, "");
+    assert_eq!(buf, Letters::names().join(""));
+}
+
+#[test]
+fn test_for_each() {
+    let mut names = String::new();
+    enum_cycles::for_each(|l: Letters| names.push_str(l.name()));
+
+    assert_eq!(names, "ABC");
+}
+
+#[test]
+fn test_cursor() {
+    let mut cursor = Cursor::new(Seven);
+
+    assert_eq!(cursor.peek(), Eight);
+    assert_eq!(cursor.next(), Some(Eight));
+    assert_eq!(cursor.next(), Some(Nine));
+    assert_eq!(cursor.next(), Some(Zero));
+    assert_eq!(cursor.peek(), One);
+    assert_eq!(cursor.by_ref().take(3).collect::<Vec<_>>(), [One, Two, Three]);
+    assert_eq!(cursor.into_inner(), Three);
+}
+
+#[test]
+fn test_cursor_clamped() {
+    let mut cursor = Cursor::new(Clamping::Mid);
+
+    assert_eq!(cursor.next(), Some(Clamping::High));
+    assert_eq!(cursor.peek(), Clamping::Low);
+    assert_eq!(cursor.next(), Some(Clamping::Low));
+    assert_eq!(cursor.into_inner(), Clamping::Low);
+}
+
+#[test]
+fn test_history() {
+    let mut history = StateHistory::new(Zero);
+    history.advance();
+    history.set(Five);
+
+    assert_eq!(history.current(), &Five);
+    assert!(history.undo());
+    assert_eq!(history.current(), &One);
+    assert!(history.undo());
+    assert_eq!(history.current(), &Zero);
+    assert!(!history.undo());
+    assert_eq!(history.current(), &Zero);
+    assert!(history.redo());
+    assert!(history.redo());
+    assert!(!history.redo());
+    assert_eq!(history.current(), &Five);
+}
+
+#[test]
+fn test_history_discards_redo() {
+    let mut history = StateHistory::new(Zero);
+    history.set(Three);
+    history.undo();
+    history.set(Seven);
+
+    assert!(!history.redo());
+    assert!(history.undo());
+    assert_eq!(history.into_inner(), Zero);
+}
+
+#[test]
+fn test_history_limit() {
+    let mut history = StateHistory::with_limit(Zero, 3);
+    for _ in 0..5 {
+        history.advance();
+    }
+
+    assert_eq!(history.current(), &Five);
+    assert!(history.undo());
+    assert!(history.undo());
+    assert!(!history.undo());
+    assert_eq!(history.current(), &Three);
+}
+
+#[test]
+fn test_advance() {
+    let mut n = Eight;
+    assert!(n.advance());
+    assert_eq!(n, Nine);
+    assert!(!n.advance());
+    assert_eq!(n, Nine);
+}
+
+#[test]
+fn test_retreat() {
+    let mut n = One;
+    assert!(n.retreat());
+    assert_eq!(n, Zero);
+    assert!(!n.retreat());
+    assert_eq!(n, Zero);
+}
+
+#[test]
+fn test_toggle() {
+    let mut switch = Switch::On;
+    for expected in [Switch::Off, Switch::On, Switch::Off, Switch::On] {
+        switch.toggle();
+        assert_eq!(switch, expected);
+    }
+    let mut latch = Latch::Open;
+    for expected in [Latch::Shut, Latch::Open, Latch::Shut, Latch::Open] {
+        latch.toggle();
+        assert_eq!(latch, expected);
+    }
+}
+
+#[test]
+fn test_window() {
+    assert_eq!(Five.window(2), [Three, Four, Five, Six, Seven]);
+    assert_eq!(Five.window(0), [Five]);
+    assert_eq!(Zero.window(1), [Nine, Zero, One]);
+    assert_eq!(B.window(2), [C, A, B, C, A]);
+}
+
+#[test]
+fn test_clamp_to_range() {
+    let mut n = Two;
+    n.clamp_to_range(3, 6);
+    assert_eq!(n, Three);
+
+    let mut n = Eight;
+    n.clamp_to_range(3, 6);
+    assert_eq!(n, Six);
+
+    let mut n = Five;
+    n.clamp_to_range(3, 6);
+    assert_eq!(n, Five);
+
+    let mut n = Zero;
+    n.clamp_to_range(12, 20);
+    assert_eq!(n, Nine);
+}
+
+#[test]
+fn test_skip_in_range() {
+    let mut n = Five;
+    n.skip_in_range(1, 3, 6);
+    assert_eq!(n, Six);
+    n.skip_in_range(1, 3, 6);
+    assert_eq!(n, Three);
+    n.skip_in_range(4, 3, 6);
+    assert_eq!(n, Three);
+    n.skip_in_range(10, 3, 6);
+    assert_eq!(n, Five);
+
+    n.skip_in_range(1, 6, 3);
+    assert_eq!(n, Five);
+    n.skip_in_range(1, 3, 10);
+    assert_eq!(n, Five);
+    n.skip_in_range(1, 6, 8);
+    assert_eq!(n, Five);
+}
+
+#[test]
+fn test_assign_checked() {
+    let mut n = Zero;
+    assert!(n.assign_checked(Seven));
+    assert_eq!(n, Seven);
+    assert!(n.assign_checked(Nine));
+    assert_eq!(n, Nine);
+
+    let mut s = Sparse::A;
+    assert!(s.assign_checked(Sparse::D));
+    assert_eq!(s, Sparse::D);
+}
+
+#[test]
+fn test_skip_reporting() {
+    let mut n = Seven;
+    assert!(!n.skip_reporting(2));
+    assert_eq!(n, Nine);
+    assert!(n.skip_reporting(1));
+    assert_eq!(n, Nine);
+    assert!(!n.skip_reporting(0));
+
+    let mut n = Five;
+    assert!(n.skip_reporting(usize::MAX));
+    assert_eq!(n, Nine);
+}
+
+#[test]
+fn test_skip_backward_reporting() {
+    let mut n = Two;
+    assert!(!n.skip_backward_reporting(2));
+    assert_eq!(n, Zero);
+    assert!(n.skip_backward_reporting(1));
+    assert_eq!(n, Zero);
+    assert!(!n.skip_backward_reporting(0));
+
+    let mut n = Five;
+    assert!(n.skip_backward_reporting(8));
+    assert_eq!(n, Zero);
+}
+
+#[test]
+fn test_checked_skip() {
+    assert_eq!(Seven.checked_skip(0), Some(Seven));
+    assert_eq!(Seven.checked_skip(2), Some(Nine));
+    assert_eq!(Seven.checked_skip(3), None);
+    assert_eq!(Seven.checked_skip(usize::MAX), None);
+}
+
+#[test]
+fn test_checked_skip_backward() {
+    assert_eq!(Two.checked_skip_backward(0), Some(Two));
+    assert_eq!(Two.checked_skip_backward(2), Some(Zero));
+    assert_eq!(Two.checked_skip_backward(3), None);
+    assert_eq!(Two.checked_skip_backward(usize::MAX), None);
+}
+
+#[test]
+fn test_step_mod() {
+    let mut n = Numbers::Three;
+
+    n.step_mod(1, 3);
+    assert_eq!(n, Four);
+    n.step_mod(2, 3);
+    assert_eq!(n, Three);
+    n.step_mod(-1, 3);
+    assert_eq!(n, Five);
+    n.step_mod(-7, 3);
+    assert_eq!(n, Four);
+
+    let mut n = Numbers::Nine;
+
+    n.step_mod(1, 3);
+    assert_eq!(n, Nine); // The last block only contains one value.
+}
+
+#[test]
+fn test_try_next() {
+    let mut n = Seven;
+    assert!(!n.try_next());
+    assert_eq!(n, Eight);
+    assert!(!n.try_next());
+    assert_eq!(n, Nine);
+    assert!(n.try_next());
+    assert_eq!(n, Zero);
+    assert!(!n.try_next());
+}
+
+#[test]
+fn test_try_previous() {
+    let mut n = One;
+    assert!(!n.try_previous());
+    assert_eq!(n, Zero);
+    assert!(n.try_previous());
+    assert_eq!(n, Nine);
+    assert!(!n.try_previous());
+}
+
+#[test]
+fn test_try_next_clamped() {
+    let mut c = Clamping::last();
+    assert!(!c.try_next());
+    assert_eq!(c, Clamping::last());
+}
+
+#[test]
+fn test_bounds() {
+    assert_eq!(Numbers::bounds(), (Zero, Nine));
+    assert_eq!(Sparse::bounds(), (Sparse::A, Sparse::D));
+}
+
+#[test]
+fn test_is_default() {
+    assert!(One.is_default());
+    assert!(!Zero.is_default());
+    assert!(Seasons::Summer.is_default());
+    assert!(Outer::NumLast(Nine).is_default());
+    assert!(Outer::NumLast(Zero).is_default());
+    assert!(!Outer::LetManual(B).is_default());
+}
+
+#[test]
+fn test_reverse_index() {
+    assert_eq!(Zero.reverse_index(), 9);
+    assert_eq!(Nine.reverse_index(), 0);
+    assert_eq!(Sparse::C.reverse_index(), 1);
+}
+
+#[test]
+fn test_distance_to() {
+    assert_eq!(Two.distance_to(&Five), 3);
+    assert_eq!(Five.distance_to(&Two), 7);
+    assert_eq!(Five.distance_to(&Five), 0);
+}
+
+#[test]
+fn test_ordinal_wrapping_offset() {
+    assert_eq!(Seven.ordinal_wrapping_offset(2), 9);
+    assert_eq!(Seven.ordinal_wrapping_offset(5), 2);
+    assert_eq!(Two.ordinal_wrapping_offset(-3), 9);
+    assert_eq!(Two.ordinal_wrapping_offset(-22), 0);
+    assert_eq!(Two.ordinal_wrapping_offset(isize::MAX), 9);
+    assert_eq!(Two.ordinal_wrapping_offset(isize::MIN), 4);
+    assert_eq!(Sparse::C.ordinal_wrapping_offset(1), 3);
+}
+
+#[test]
+fn test_index_wrapping_offset() {
+    assert_eq!(Seven.index_wrapping_offset(5), 2);
+    assert_eq!(Two.index_wrapping_offset(-3), 9);
+    assert_eq!(Sparse::C.index_wrapping_offset(1), 12);
+    assert_eq!(Sparse::D.index_wrapping_offset(1), 3);
+    assert_eq!(Steps::Start.index_wrapping_offset(1), 2);
+    assert_eq!(Steps::Hidden(3).index_wrapping_offset(0), 1);
+    assert_eq!(Steps::Hidden(3).index_wrapping_offset(1), 2);
+}
+
+#[test]
+fn test_shortest_signed_distance() {
+    assert_eq!(Two.shortest_signed_distance(&Five), 3);
+    assert_eq!(Five.shortest_signed_distance(&Two), -3);
+    assert_eq!(One.shortest_signed_distance(&Eight), -3);
+    assert_eq!(Eight.shortest_signed_distance(&One), 3);
+    assert_eq!(Zero.shortest_signed_distance(&Five), 5);
+    assert_eq!(Five.shortest_signed_distance(&Zero), 5);
+    assert_eq!(Four.shortest_signed_distance(&Four), 0);
+}
+
+#[test]
+fn test_cycle_to() {
+    let mut n = Two;
+    let mut path = Vec::new();
+    while n != Nine {
+        n.cycle_to(&Nine);
+        path.push(n.clone());
+    }
+    assert_eq!(path, [One, Zero, Nine]);
+
+    let mut n = Three;
+    for _ in 0..10 {
+        n.cycle_to(&Six);
+    }
+    assert_eq!(n, Six);
+    n.cycle_to(&Six);
+    assert_eq!(n, Six);
+}
+
+#[test]
+fn test_within() {
+    assert!(Four.within(&Six, 2));
+    assert!(!Four.within(&Seven, 2));
+    assert!(Six.within(&Four, 2));
+    assert!(Nine.within(&One, 2));
+    assert!(One.within(&Nine, 2));
+    assert!(!One.within(&Eight, 2));
+    assert!(Five.within(&Five, 0));
+    assert!(Zero.within(&Five, 5));
+    assert!(!Zero.within(&Five, 4));
+    assert!(Zero.within(&Five, 100));
+}
+
+#[test]
+fn test_linear_distance() {
+    assert_eq!(Two.linear_distance(&Five), 3);
+    assert_eq!(Five.linear_distance(&Two), 3);
+    assert_eq!(Five.distance_to(&Two), 7);
+    assert_eq!(Nine.linear_distance(&Zero), 9);
+    assert_eq!(Nine.distance_to(&Zero), 1);
+    assert_eq!(Five.linear_distance(&Five), 0);
+}
+
+#[test]
+fn test_count_between() {
+    assert_eq!(Numbers::count_between(&Two, &Five), 4);
+    assert_eq!(Numbers::count_between(&Five, &Two), 8);
+    assert_eq!(Numbers::count_between(&Five, &Five), 1);
+    assert_eq!(Numbers::count_between(&Zero, &Nine), 10);
+}
+
+#[test]
+fn test_difference_name() {
+    assert_eq!(Eight.difference_name(&One), ("One", 3));
+    assert_eq!(One.difference_name(&Eight), ("Eight", 7));
+}
+
+#[test]
+fn test_from_name() {
+    assert_eq!(Numbers::from_name("Zero"), Some(Zero));
+    assert_eq!(Numbers::from_name("Nine"), Some(Nine));
+    assert_eq!(Numbers::from_name("Ten"), None);
+    assert_eq!(Numbers::from_name("zero"), None);
+}
+
+#[test]
+fn test_min_max() {
+    assert_eq!(Numbers::min(), Zero);
+    assert_eq!(Numbers::max(), Nine);
+    assert_eq!(Letters::min(), A);
+    assert_eq!(Letters::max(), C);
+    assert_eq!(Rank::min(), Rank::Bronze);
+    assert_eq!(Rank::max(), Rank::Platinum);
+    assert_eq!(Rank::Gold.min(Rank::Silver), Rank::Silver);
+}
+
+#[test]
+fn test_ext_vecs() {
+    let mut values = Letters::to_vec();
+    values.reverse();
+
+    assert_eq!(values, [C, B, A]);
+    assert_eq!(Numbers::to_vec(), Numbers::values());
+    assert_eq!(Letters::names_vec(), Letters::names());
+}
+
+#[test]
+fn test_names_range() {
+    assert_eq!(Numbers::names_range(2..5), ["Two", "Three", "Four"]);
+    assert_eq!(Numbers::names_range(8..20), ["Eight", "Nine"]);
+    assert!(Numbers::names_range(12..20).is_empty());
+}
+
+#[test]
+fn test_values_range() {
+    assert_eq!(Numbers::values_range(2..5), [Two, Three, Four]);
+    assert_eq!(Numbers::values_range(8..20), [Eight, Nine]);
+    assert!(Numbers::values_range(12..20).is_empty());
+}
+
+#[test]
+fn test_repr_u8() {
+    for value in Priority::values() {
+        assert_eq!(Priority::from_u8(value.to_u8()).as_ref(), Some(value));
+    }
+    assert_eq!(Priority::Urgent.to_u8(), 2);
+    assert_eq!(Priority::from_u8(3), None);
+}
+
+#[test]
+fn test_as_ref_str() {
+    fn shout(s: impl AsRef<str>) -> String {
+        s.as_ref().to_uppercase()
+    }
+    assert_eq!(shout(Planet::Venus), "VENUS");
+    assert_eq!(Planet::Earth.as_ref(), "Earth");
+}
+
+#[test]
+fn test_into_primitives() {
+    let index: usize = Weekday::Thursday.into();
+    let name: &str = Weekday::Thursday.into();
+
+    assert_eq!(index, 3);
+    assert_eq!(name, "Thursday");
+}
+
+#[test]
+fn test_indices() {
+    assert_eq!(Letters::indices(), [0, 1, 2]);
+    assert_eq!(Sparse::VARIANT_INDICES, [3, 7, 8, 12]);
+    assert_eq!(Steps::indices(), [0, 2, 3]);
+
+    let sparse: Vec<_> = Sparse::indices().iter().map(|&i| Sparse::from_index(i).unwrap()).collect();
+    let steps: Vec<_> = Steps::indices().iter().map(|&i| Steps::from_index(i).unwrap()).collect();
+    assert_eq!(sparse, Sparse::values());
+    assert_eq!(steps, Steps::values());
+}
+
+#[test]
+fn test_index_name_pairs() {
+    assert_eq!(Letters::index_name_pairs(), [(0, "A"), (1, "B"), (2, "C")]);
+    assert_eq!(Sparse::index_name_pairs()[0], (3, "A"));
+}
+
+#[test]
+fn test_index_checked() {
+    assert_eq!(Numbers::index_checked(3), Ok(Three));
+    assert_eq!(Numbers::index_checked(10), Err(IndexOutOfRange { index: 10, size: 10 }));
+    assert_eq!(Sparse::index_checked(0), Err(IndexOutOfRange { index: 0, size: 4 }));
+    assert_eq!(Sparse::index_checked(12), Ok(Sparse::D));
+    assert_eq!(Sparse::index_checked(1).unwrap_err().to_string(), "1 is not a valid index");
+}
+
+#[test]
+fn test_index_checked_propagates() {
+    fn second_name(i: usize) -> Result<&'static str, IndexOutOfRange> {
+        Ok(Numbers::index_checked(i)?.peek_next().name())
+    }
+    assert_eq!(second_name(4), Ok("Five"));
+    assert_eq!(second_name(12).unwrap_err().to_string(), "12 is not a valid index");
+}
+
+#[test]
+fn test_with_index() {
+    assert_eq!(Numbers::with_index(3), Some(Three));
+    assert_eq!(Numbers::with_index(10), None);
+    assert_eq!(Sparse::with_index(8), Some(Sparse::C));
+    assert_eq!(Sparse::with_index(0), None);
+}
+
+#[test]
+fn test_with_name() {
+    assert_eq!(Numbers::with_name("Six"), Some(Six));
+    assert_eq!(Numbers::with_name("six"), None);
+    assert_eq!(Seasons::with_name("Fall"), Some(Seasons::Autumn));
+}
+
+#[test]
+fn test_contains_name() {
+    assert!(Numbers::contains_name("Five"));
+    assert!(!Numbers::contains_name("Ten"));
+    assert!(Seasons::contains_name("Fall"));
+    assert!(!Seasons::contains_name("Autumn"));
+}
+
+#[test]
+fn test_rename() {
+    assert_eq!(Seasons::Autumn.name(), "Fall");
+    assert_eq!(Seasons::names(), ["Spring", "Summer", "Fall", "Winter"]);
+    assert_eq!(Seasons::from_name("Fall"), Some(Seasons::Autumn));
+}
+
+#[test]
+fn test_description() {
+    assert_eq!(Seasons::Summer.description(), "The warmest season.");
+    assert_eq!(Seasons::Autumn.description(), "The season in which leaves fall.\n\nAlso known as autumn.");
+    assert_eq!(Seasons::Spring.description(), "");
+}
+
+#[test]
+fn test_raw_identifiers() {
+    assert_eq!(Keywords::r#Match.name(), "Match");
+    assert_eq!(Keywords::names(), ["Match", "type"]);
+    assert_eq!(Keywords::from_name("type"), Some(Keywords::r#type));
+    assert_eq!(Keywords::from_name("r#type"), None);
+}
+
+#[test]
+fn test_index_of_name() {
+    assert_eq!(Numbers::index_of_name("Four"), Some(4));
+    assert_eq!(Numbers::index_of_name("Zero"), Some(0));
+    assert_eq!(Numbers::index_of_name("Nine"), Some(9));
+    assert_eq!(Numbers::index_of_name("Ten"), None);
+    assert_eq!(Sparse::index_of_name("C"), Some(8));
+    assert_eq!(Steps::index_of_name("Middle"), Some(2));
+    assert_eq!(Steps::index_of_name("Hidden"), None);
+    assert_eq!(Dynamic::index_of_name("Runtime"), Some(1));
+}
+
+#[test]
+fn test_saturating_set_index() {
+    let mut n = Zero;
+    n.saturating_set_index(4);
+    assert_eq!(n, Four);
+    n.saturating_set_index(10);
+    assert_eq!(n, Nine);
+    n.saturating_set_index(usize::MAX);
+    assert_eq!(n, Nine);
+}
+
+#[test]
+fn test_wrapping_set_index() {
+    let mut n = Zero;
+    n.wrapping_set_index(4);
+    assert_eq!(n, Four);
+    n.wrapping_set_index(10);
+    assert_eq!(n, Zero);
+    n.wrapping_set_index(23);
+    assert_eq!(n, Three);
+}
+
+#[test]
+fn test_cycle_between() {
+    let mut n = Numbers::Two;
+    n.cycle_between(&Numbers::Two, &Numbers::Seven);
+    assert_eq!(n, Numbers::Seven);
+    n.cycle_between(&Numbers::Two, &Numbers::Seven);
+    assert_eq!(n, Numbers::Two);
+    let mut n = Numbers::Four;
+    n.cycle_between(&Numbers::Two, &Numbers::Seven);
+    assert_eq!(n, Numbers::Two);
+}
+
+#[test]
+fn test_find_first() {
+    assert_eq!(Numbers::find_first(|n| n.index() % 2 == 0), Some(Numbers::Zero));
+    assert_eq!(Numbers::find_first(|n| n.index() > 7), Some(Numbers::Eight));
+    assert_eq!(Numbers::find_first(|n| n.index() > 9), None);
+    assert_eq!(Numbers::find_index(|n| n.index() > 7), Some(8));
+    assert_eq!(Sparse::find_index(|s| s.name() == "C"), Some(2));
+    assert_eq!(Sparse::find_index(|_| false), None);
+}
+
+#[test]
+fn test_from_name_large() {
+    for (ordinal, name) in Large::names().iter().enumerate() {
+        let value = Large::from_name(name).unwrap();
+        assert_eq!(value.ordinal(), ordinal);
+        assert_eq!(value.name(), *name);
+    }
+    assert_eq!(Large::from_name("V200"), None);
+    assert_eq!(Large::from_name(""), None);
+}
+
+/// Compares the generated `from_name` against a linear scan of `names()`.
+/// Run with `cargo test --release -- --ignored --nocapture bench_from_name`.
+#[test]
+#[ignore]
+fn bench_from_name() {
+    use std::hint::black_box;
+    use std::time::Instant;
+
+    const ITERATIONS: usize = 100_000;
+    let names = Large::names();
+    let start = Instant::now();
+    for i in 0..ITERATIONS {
+        black_box(Large::from_name(black_box(names[i % names.len()])));
+    }
+    let search = start.elapsed();
+
+    let start = Instant::now();
+    for i in 0..ITERATIONS {
+        let name = black_box(names[i % names.len()]);
+        black_box(names.iter().position(|n| *n == name).and_then(Large::from_ordinal));
+    }
+    let scan = start.elapsed();
+
+    println!("binary search: {:?}, linear scan: {:?}", search, scan);
+}
+
+#[test]
+fn test_try_from() {
+    assert_eq!(Signal::try_from(0), Ok(Signal::Red));
+    assert_eq!(Signal::try_from(2), Ok(Signal::Green));
+    assert_eq!(Signal::try_from(3), Err(SignalIndexError(3)));
+    assert_eq!(Signal::try_from("Amber"), Ok(Signal::Amber));
+    assert_eq!(Signal::try_from("Blue"), Err(SignalNameError("Blue")));
+
+    assert_eq!(SignalIndexError(3).to_string(), "3 is not a valid index for Signal");
+    assert_eq!(SignalNameError("Blue").to_string(), "`Blue` is not a valid name for Signal");
+}
+
+#[test]
+fn test_grid_position() {
+    assert_eq!(Seven.grid_position(3), (2, 1));
+    assert_eq!(Zero.grid_position(3), (0, 0));
+    assert_eq!(Nine.grid_position(10), (0, 9));
+    assert_eq!(Nine.grid_position(1), (9, 0));
+}
+
+#[test]
+fn test_properties() {
+    let names = [
+        "Zero", "One", "Two", "Three", "Four",
+        "Five", "Six", "Seven", "Eight", "Nine"
+    ];
+    let values = [
+        Zero, One, Two, Three, Four,
+        Five, Six, Seven, Eight, Nine
+    ];
+
+    assert_eq!(Numbers::names(), names);
+    assert_eq!(Numbers::values(), values);
+    assert_eq!(Numbers::first(), Zero);
+    assert_eq!(Numbers::last(), Nine);
+    assert_eq!(Numbers::size(), 10);
+}
+
+#[test]
+fn test_ord() {
+    use Rank::*;
+    let mut shuffled = vec![Gold, Bronze, Platinum, Silver];
+    shuffled.sort();
+
+    assert!(Silver < Gold);
+    assert!(Platinum > Bronze);
+    assert_eq!(shuffled, Rank::values());
+
+    let map: BTreeMap<_, _> = vec![(Gold, "c"), (Bronze, "a"), (Silver, "b")].into_iter().collect();
+    assert_eq!(map.values().cloned().collect::<Vec<_>>(), ["a", "b", "c"]);
+}
+
+#[test]
+fn test_hash() {
+    use Suit::*;
+    let map: HashMap<_, _> = Suit::values().iter()
+        .map(|s| (s.clone(), s.name()))
+        .collect();
+
+    assert_eq!(map.len(), Suit::size());
+    assert_eq!(map[&Clubs], "Clubs");
+    assert_eq!(map[&Hearts], "Hearts");
+    assert_eq!(map[&Spades], "Spades");
+}
+
+#[test]
+fn test_variants_const() {
+    const NAMES: &[&str] = Numbers::VARIANTS;
+
+    assert_eq!(NAMES.len(), 10);
+    assert_eq!(NAMES[0], "Zero");
+    assert_eq!(NAMES[9], "Nine");
+    assert_eq!(Numbers::VARIANTS, Numbers::names());
+}
+
+#[test]
+fn test_tuple_defaults() {
+    assert_eq!(Pairs::values(), [
+        Pairs::Single(Three),
+        Pairs::Listed(One, B),
+        Pairs::Grouped(Two, C),
+        Pairs::Tuple((4, 5)),
+    ]);
+}
+
+#[test]
+fn test_names_joined() {
+    assert_eq!(Letters::NAMES_JOINED, "A, B, C");
+    assert_eq!(Sparse::NAMES_JOINED, "A, B, C, D");
+}
+
+#[test]
+fn test_from_index_const() {
+    const LOOKUP: [Option<Letters>; 4] = [
+        Letters::from_index_const(2),
+        Letters::from_index_const(1),
+        Letters::from_index_const(0),
+        Letters::from_index_const(3),
+    ];
+
+    assert_eq!(LOOKUP, [Some(C), Some(B), Some(A), None]);
+}
+
+#[test]
+fn test_start_at() {
+    use Rotated::*;
+    assert_eq!(Rotated::values(), [Two, Three, Zero, One]);
+    assert_eq!(Rotated::names(), ["Two", "Three", "Zero", "One"]);
+    assert_eq!(Rotated::bounds(), (Two, One));
+    assert_eq!(Two.ordinal(), 0);
+    assert_eq!(Two.index(), 0);
+    assert_eq!(Zero.index(), 2);
+    assert_eq!(One.index(), 3);
+    assert_eq!(Rotated::from_index(0), Some(Two));
+    assert_eq!(Rotated::from_index(4), None);
+    assert_eq!(Rotated::indices(), [0, 1, 2, 3]);
+    assert_eq!(Rotated::TWO_INDEX, 0);
+    assert_eq!(Rotated::default(), Two);
+
+    for value in Rotated::values() {
+        assert_eq!(Rotated::from_index(value.index()).as_ref(), Some(value));
+    }
+
+    let mut r = Three;
+    r.next();
+    assert_eq!(r, Zero);
+    assert!(!r.try_next());
+    assert!(r.try_next());
+    assert_eq!(r, Two);
+}
+
+#[test]
+fn test_phantom_data() {
+    use Tagged::*;
+    assert_eq!(Tagged::values(), [
+        Marker(PhantomData),
+        Mixed(A, PhantomData),
+        Named { letter: A, number: Nine, marker: PhantomData }
+    ]);
+    assert_eq!(Tagged::total_states(), 1 + 3 + 30);
+
+    let mut t = Marker(PhantomData);
+    t.deep_next();
+    assert_eq!(t, Mixed(A, PhantomData));
+    t.deep_next();
+    assert_eq!(t, Mixed(B, PhantomData));
+}
+
+#[test]
+fn test_non_exhaustive() {
+    assert_eq!(Open::Second.index(), 1);
+    assert_eq!(Open::First.name(), "First");
+    assert_eq!(Open::First.peek_next(), Open::Second);
+    assert_eq!(Open::from_name("Second"), Some(Open::Second));
+}
+
+#[test]
+fn test_default_expr() {
+    use Dynamic::*;
+    assert_eq!(Dynamic::values(), [Constant(Letters::C), Runtime(Letters::A), Named { letter: Letters::A, n: 0 }]);
+    assert_eq!(Dynamic::from_ordinal(0), Some(Constant(Letters::C)));
+    assert_eq!(Dynamic::from_ordinal(1), Some(Runtime(Letters::C)));
+    assert_eq!(Dynamic::from_name("Named"), Some(Named { letter: Letters::B, n: 10 }));
+    assert_eq!(Dynamic::from_ordinal(3), None);
+
+    let mut d = Dynamic::first();
+    d.next();
+    assert_eq!(d, Runtime(Letters::C));
+    assert_eq!(Dynamic::first().offset(1), Dynamic::Runtime(Letters::C));
+
+    let mut d = Dynamic::first();
+    d.cycle_to(&Runtime(Letters::A));
+    assert_eq!(d, Runtime(Letters::C));
+}
+
+#[test]
+fn test_index_consts() {
+    const TABLE: [u8; Numbers::NINE_INDEX + 1] = [0; 10];
+
+    assert_eq!(TABLE.len(), 10);
+    assert_eq!(Numbers::ZERO_INDEX, 0);
+    assert_eq!(Sparse::C_INDEX, 8);
+    assert_eq!(Keywords::MATCH_INDEX, 0);
+    assert!(matches!(Four.index(), i if i == Numbers::FOUR_INDEX));
+}
+
+#[test]
+fn test_discriminant() {
+    const PONG: u16 = Protocol::Pong.discriminant();
+
+    assert_eq!(PONG, 0x11);
+    assert_eq!(Protocol::Ping.discriminant(), 0x10);
+    assert_eq!(Protocol::Close.discriminant(), 0x201);
+    assert_eq!(Protocol::Close.index(), 3);
+    assert_eq!(Sparse::C.discriminant(), 8);
+    assert_eq!(Sparse::A.discriminant(), 3);
+    assert_eq!(Numbers::Four.discriminant(), 4);
+}
+
+#[test]
+fn test_all() {
+    assert_eq!(Numbers::ALL, Numbers::values());
+    assert_eq!(Rotated::ALL.first(), Some(&Rotated::Two));
+    assert_eq!(Outer::ALL.len(), Outer::size());
+}
+
+#[test]
+fn test_name_index() {
+    const FALL: Option<usize> = Seasons::name_index(b"Fall");
+    const C: Option<usize> = Sparse::name_index(b"C");
+
+    assert_eq!(FALL, Some(2));
+    assert_eq!(C, Some(8));
+    assert_eq!(Seasons::name_index(b"Autumn"), None);
+}
+
+#[test]
+fn test_from_index_const_fields() {
+    const STATES: [Outer; 4] = [
+        Outer::from_index_const(0).unwrap(),
+        Outer::from_index_const(1).unwrap(),
+        Outer::from_index_const(2).unwrap(),
+        Outer::from_index_const(3).unwrap(),
+    ];
+
+    assert_eq!(STATES, Outer::values());
+    assert_eq!(Outer::from_index_const(4), None);
+    assert_eq!(Shapes::from_index_const(1), Some(Shapes::Line { length: 1, direction: Two }));
+}
+
+#[test]
+fn test_copy() {
+    let iterations = 1_000_001;
+    let mut c = Compass::North;
+    let mut o = Overrides::NumFirst(Zero); // Not `Copy`, uses `clone()`.
+
+    for _ in 0..iterations {
+        c.next();
+        o.previous();
+    }
+    assert_eq!(c, Compass::East);
+    assert_eq!(o, Overrides::NumAuto(One));
+    assert_eq!(Compass::from_index(3), Some(Compass::West));
+    assert_eq!(Compass::from_index(4), None);
+}
+
+#[test]
+fn test_default_variant() {
+    assert_eq!(Seasons::default(), Seasons::Summer);
+    assert_eq!(Seasons::first(), Seasons::Spring);
+    assert_eq!(Seasons::values()[1], Seasons::Summer);
+}
+
+#[test]
+fn test_std_default() {
+    assert_eq!(<Volume as Default>::default(), Volume::Medium);
+    assert_eq!(Volume::from_name("Loudest").unwrap_or_default(), Volume::Medium);
+}
+
+#[test]
+fn test_auto_uses_field_defaults() {
+    assert_eq!(Numbers::default(), One);
+    assert_eq!(Letters::default(), Letters::first());
+    assert_eq!(Outer::values()[2], NumAuto(Numbers::default()));
+    assert_eq!(Outer::values()[3], LetAuto(Letters::default()));
+}
+
+#[test]
+fn test_variant_auto() {
+    let values = [
+        Overrides::NumFirst(Zero), // Top-level #[first]
+        Overrides::NumAuto(One),   // #[auto] uses the specified default
+        Overrides::LetAuto(A),     // #[auto] falls back to the first value
+    ];
+
+    assert_eq!(Overrides::values(), values);
+}
+
+#[test]
+fn test_by_discriminant() {
+    assert_eq!(Sparse::values(), [Sparse::A, Sparse::B, Sparse::C, Sparse::D]);
+    assert_eq!(Sparse::names(), ["A", "B", "C", "D"]);
+    assert_eq!(Sparse::C.index(), 8);
+    assert_eq!(Sparse::C.ordinal(), 2);
+    assert_eq!(Sparse::from_index(12), Some(Sparse::D));
+    assert_eq!(Sparse::from_index(4), None);
+    assert_eq!(Sparse::from_index_const(7), Some(Sparse::B));
+
+    let mut s = Sparse::A;
+    let mut indices = Vec::new();
+    for _ in 0..5 {
+        s.next();
+        indices.push(s.index());
+    }
+    assert_eq!(indices, [7, 8, 12, 3, 7]);
+
+    s.previous();
+    assert_eq!(s, Sparse::A);
+    s.previous();
+    assert_eq!(s, Sparse::D);
+}
+
+#[test]
+fn test_deep_next() {
+    let mut expected = Vec::new();
+    expected.extend(Numbers::values().iter().cloned().map(NumLast));
+    expected.push(LetManual(B)); // Explicit defaults are not traversed.
+    expected.extend(Numbers::values().iter().cloned().map(NumAuto));
+    expected.extend(Letters::values().iter().cloned().map(LetAuto));
+
+    let mut o = Outer::deep_first();
+    let mut states = Vec::new();
+    for _ in 0..expected.len() {
+        states.push(o.clone());
+        o.deep_next();
+    }
+    assert_eq!(states, expected);
+    assert_eq!(o, Outer::deep_first());
+
+    let mut states = Vec::new();
+    for _ in 0..expected.len() {
+        o.deep_previous();
+        states.push(o.clone());
+    }
+    expected.reverse();
+    assert_eq!(states, expected);
+    assert_eq!(Outer::deep_last(), LetAuto(C));
+}
+
+#[test]
+fn test_deep_next_fields() {
+    let mut s = Shapes::Plane { width: A, height: Nine };
+
+    s.deep_next(); // The last field is stepped first.
+    assert_eq!(s, Shapes::Plane { width: B, height: Zero });
+    s.deep_previous();
+    assert_eq!(s, Shapes::Plane { width: A, height: Nine });
+
+    let mut s = Shapes::Plane { width: C, height: Nine };
+    s.deep_next();
+    assert_eq!(s, Shapes::Point);
+    s.deep_previous();
+    assert_eq!(s, Shapes::Plane { width: C, height: Nine });
+}
+
+#[test]
+fn test_total_states() {
+    let mut o = Outer::deep_first();
+    let mut count = 0;
+    loop {
+        o.deep_next();
+        count += 1;
+        if o == Outer::deep_first() {
+            break;
+        }
+    }
+
+    assert_eq!(Outer::total_states(), 24);
+    assert_eq!(Outer::total_states(), count);
+    assert_eq!(Shapes::total_states(), 1 + 1 + 3 * 10);
+    assert_eq!(Numbers::total_states(), Numbers::size());
+}
+
+#[test]
+fn test_all_deep_states() {
+    let states = Outer::all_deep_states();
+
+    assert_eq!(states.len(), Outer::total_states());
+    assert_eq!(states[0], NumLast(Zero));
+    assert_eq!(states[9], NumLast(Nine));
+    assert_eq!(states[10], LetManual(B));
+    assert_eq!(states[11], NumAuto(Zero));
+    assert_eq!(states[23], LetAuto(C));
+    assert_eq!(Letters::all_deep_states(), Letters::values());
+}
+
+#[test]
+fn test_defaults() {
+    let values = [
+        NumLast(Nine), // #[last] overrides #[auto]
+        LetManual(B),  // #[default(...)] overrides #[auto]
+        NumAuto(One),  // Default specified, used by #[auto]
+        LetAuto(A),    // No default => #[auto] uses first
+    ];
+
+    assert_eq!(Outer::values(), values);
+}
+
+#[deny(deprecated)]
+mod deprecated {
+    use enum_cycles::EnumState;
+
+    #[derive(Debug, PartialEq, Clone, EnumState)]
+    pub enum Versions {
+        #[deprecated]
+        Legacy,
+        Current
+    }
+
+    #[test]
+    fn test_deprecated() {
+        let mut v = Versions::Current;
+        v.next();
+        assert_eq!(v.name(), "Legacy");
+        assert_eq!(Versions::names(), ["Legacy", "Current"]);
+    }
+}
+
+#[test]
+fn test_cycle_names() {
+    let names: Vec<_> = Eight.cycle_names().collect();
+
+    assert_eq!(names[..3], ["Eight", "Nine", "Zero"]);
+    assert_eq!(names.len(), Numbers::size());
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+enum Shapes {
+    Point,
+    #[default { length: 1, direction: Two }]
+    Line { length: u8, direction: Numbers },
+    #[first]
+    Plane { width: Letters, height: Numbers },
+}
+
+#[test]
+fn test_named_fields() {
+    let values = [
+        Shapes::Point,
+        Shapes::Line { length: 1, direction: Two }, // Struct-literal default
+        Shapes::Plane { width: A, height: Zero },   // Attribute applied per field
+    ];
+
+    assert_eq!(Shapes::values(), values);
+    assert_eq!(Shapes::Line { length: 5, direction: Six }.index(), 1);
+    assert_eq!(Shapes::Plane { width: C, height: Nine }.name(), "Plane");
+}
+
+mod helpers {
+    use std::convert::TryFrom;
+
+    #[derive(Debug, PartialEq, Clone, EnumState)]
+    #[try_from]
+    enum Red {
+        Light,
+        Dark
+    }
+
+    #[derive(Debug, PartialEq, Clone, EnumState)]
+    #[try_from]
+    enum Blue {
+        Light,
+        Dark
+    }
+
+    #[test]
+    fn test_helper_modules() {
+        assert_eq!(Red::try_from("Dark"), Ok(Red::Dark));
+        assert_eq!(Blue::try_from("Dark"), Ok(Blue::Dark));
+        assert_eq!(Red::try_from(2), Err(RedIndexError(2)));
+        assert_eq!(Blue::try_from("Navy"), Err(BlueNameError("Navy")));
+    }
+
+    #[test]
+    fn test_helper_types_in_fn() {
+        #[derive(Debug, PartialEq, Clone, EnumState)]
+        #[try_from]
+        enum Green {
+            Light,
+            Dark
+        }
+
+        assert_eq!(Green::try_from(1), Ok(Green::Dark));
+        assert_eq!(Green::try_from("Lime"), Err(GreenNameError("Lime")));
+    }
+}
+
+mod reexports {
+    pub mod cycles {
+        pub use enum_cycles::EnumState;
+    }
+
+    pub mod consumer {
+        use super::cycles::EnumState as Cycle;
+
+        #[derive(Debug, PartialEq, Clone, EnumState)]
+        #[enum_state(crate = "crate::reexports::cycles")]
+        #[auto]
+        #[try_from]
+        pub enum Light {
+            Off,
+            On(crate::Letters)
+        }
+
+        #[test]
+        fn test_crate_path() {
+            assert_eq!(Light::Off.peek_next(), Light::On(crate::Letters::A));
+            assert_eq!(Light::from_name("On"), Some(Light::On(crate::Letters::A)));
+            assert_eq!(Light::size(), 2);
+        }
+    }
+}
+
+mod unimported {
+    #[derive(Debug, PartialEq, Eq, Clone, EnumState)]
+    #[first]
+    #[ord]
+    #[hash]
+    #[try_from]
+    enum Pair {
+        Left(crate::Rank),
+        Right(crate::Rank)
+    }
+
+    #[test]
+    fn test_without_import() {
+        assert_eq!(Pair::VARIANTS, ["Left", "Right"]);
+        assert_eq!(<Pair as enum_cycles::EnumState>::first(), Pair::Left(crate::Rank::Bronze));
+        assert_eq!(<Pair as enum_cycles::EnumState>::total_states(), 8);
+    }
+}
+
+mod qualified {
+    pub mod depths {
+        pub const DEEPEST: Depth = Depth::High;
+
+        #[derive(Debug, PartialEq, Clone, EnumState)]
+        #[default(crate::qualified::depths::Depth::Mid)]
+        pub enum Depth {
+            Low,
+            Mid,
+            High
+        }
+
+        #[derive(Debug, PartialEq, Clone, EnumState)]
+        #[default(self::DEEPEST_FLOOR)]
+        pub enum Floor {
+            Top,
+            Bottom
+        }
+
+        pub const DEEPEST_FLOOR: Floor = Floor::Bottom;
+    }
+
+    use enum_cycles::EnumState;
+    use self::depths::{Depth, Floor};
+
+    #[test]
+    fn test_qualified_default() {
+        assert_eq!(Depth::default(), Depth::Mid);
+        assert_eq!(Floor::default(), Floor::Bottom);
+        assert_eq!(depths::DEEPEST.peek_next(), Depth::Low);
+    }
+}
+
+mod single {
+    use enum_cycles::EnumState;
+    use super::Single::{self, Only};
+
+    #[test]
+    fn test_single_navigation() {
+        let mut s = Only;
+        s.next();
+        s.previous();
+        s.toggle();
+        s.deep_next();
+        s.deep_previous();
+        s.skip(7);
+        s.skip_backward(7);
+        s.step_by(3, 5);
+        s.saturating_skip(2);
+        s.saturating_skip_backward(2);
+        s.step_mod(-4, 1);
+        s.clamp_to_range(0, 5);
+        s.skip_in_range(3, 0, 0);
+        assert_eq!(s, Only);
+
+        assert!(s.skip_reporting(1));
+        assert!(s.skip_backward_reporting(1));
+        assert!(s.deep_step(true));
+        assert!(s.assign_checked(Only));
+        assert_eq!(s, Only);
+    }
+
+    #[test]
+    fn test_single_queries() {
+        assert_eq!(Only.peek_next(), Only);
+        assert_eq!(Only.peek_previous(), Only);
+        assert_eq!(Only.next_n(3), [Only, Only, Only]);
+        assert_eq!(Only.previous_n(2), [Only, Only]);
+        assert_eq!(Only.window(1), [Only, Only, Only]);
+        assert_eq!(Only.wrapping_add(9), Only);
+        assert_eq!(Only.wrapping_sub(9), Only);
+        assert_eq!(Only.offset(-9), Only);
+        assert_eq!(Only.checked_skip(0), Some(Only));
+        assert_eq!(Only.checked_skip(1), None);
+        assert_eq!(Only.checked_skip_backward(1), None);
+        assert_eq!(Only.cycle_names().collect::<Vec<_>>(), ["Only"]);
+        assert_eq!(Only.cycle_forever().take(2).collect::<Vec<_>>(), [Only, Only]);
+        assert_eq!(Only.distance_to(&Only), 0);
+        assert_eq!(Only.difference_name(&Only), ("Only", 0));
+        assert_eq!(Only.grid_position(3), (0, 0));
+        assert_eq!(Only.map_index(&[1, 2]), Some(&1));
+        assert_eq!(Only.position(), 0.0);
+        assert_eq!(Only.ordinal(), 0);
+        assert_eq!(Only.index(), 0);
+        assert_eq!(Only.name(), "Only");
+    }
+
+    #[test]
+    fn test_single_constructors() {
+        assert_eq!(Single::from_index(0), Some(Only));
+        assert_eq!(Single::from_index(1), None);
+        assert_eq!(Single::from_ordinal(0), Some(Only));
+        assert_eq!(Single::from_name("Only"), Some(Only));
+        assert!(Single::contains_name("Only"));
+        assert_eq!(Single::index_of_name("Only"), Some(0));
+        assert_eq!(Single::deep_from_ordinal(0, false), Some(Only));
+        assert_eq!(Single::from_position(1.0), Only);
+        assert_eq!(Single::all_deep_states(), [Only]);
+        assert_eq!(Single::deep_first(), Only);
+        assert_eq!(Single::deep_last(), Only);
+        assert_eq!(Single::names(), ["Only"]);
+        assert_eq!(Single::values(), [Only]);
+        assert_eq!(Single::names_range(0..5), ["Only"]);
+        assert_eq!(Single::values_range(1..5), []);
+        assert_eq!(Single::index_name_pairs(), [(0, "Only")]);
+        assert_eq!(Single::default(), Only);
+        assert_eq!(Single::first(), Only);
+        assert_eq!(Single::last(), Only);
+        assert_eq!(Single::min(), Only);
+        assert_eq!(Single::max(), Only);
+        assert_eq!(Single::size(), 1);
+        assert_eq!(Single::declared_count(), 1);
+        assert_eq!(Single::total_states(), 1);
+    }
+}
@@ -0,0 +1,18 @@
+#[macro_use]
+extern crate enum_cycles_derive;
+
+use enum_cycles::EnumState;
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+#[default(Level::Medium)]
+enum Level {
+    Low,
+    Medium,
+    High
+}
+
+#[test]
+fn test_debug_feature() {
+    assert_eq!(Level::default(), Level::Medium);
+    assert_eq!(Level::High.peek_next(), Level::Low);
+}
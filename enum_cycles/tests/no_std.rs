@@ -0,0 +1,51 @@
+#![no_std]
+
+#[macro_use]
+extern crate enum_cycles_derive;
+
+use core::convert::TryFrom;
+use enum_cycles::EnumState;
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+#[default(Direction::East)]
+#[try_from]
+enum Direction {
+    North,
+    East,
+    South,
+    West
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+enum Gated {
+    Always,
+    #[cfg(feature = "std")]
+    Std
+}
+
+#[test]
+fn test_no_std() {
+    let mut d = Direction::default();
+
+    d.next();
+    assert_eq!(d, Direction::South);
+    d.skip_backward(2);
+    assert_eq!(d, Direction::North);
+    d.previous();
+    assert_eq!(d, Direction::West);
+    assert_eq!(Direction::from_index(0), Some(Direction::North));
+    assert_eq!(Direction::try_from("East"), Ok(Direction::East));
+    assert_eq!(Direction::try_from(4), Err(DirectionIndexError(4)));
+}
+
+#[test]
+fn test_cfg_variants() {
+    if cfg!(feature = "std") {
+        assert_eq!(Gated::size(), 2);
+        assert_eq!(Gated::names(), ["Always", "Std"]);
+    } else {
+        assert_eq!(Gated::size(), 1);
+        assert_eq!(Gated::names(), ["Always"]);
+    }
+    assert_eq!(Gated::Always.peek_previous(), Gated::last());
+}
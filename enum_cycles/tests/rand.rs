@@ -0,0 +1,39 @@
+#[macro_use]
+extern crate enum_cycles_derive;
+
+use enum_cycles::EnumState;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+enum Tabs {
+    Home,
+    Search,
+    Settings,
+    About
+}
+
+#[derive(Debug, PartialEq, Clone, EnumState)]
+enum Only {
+    One
+}
+
+#[test]
+fn test_random_different() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut seen = [false; 4];
+    let mut tab = Tabs::Home;
+    for _ in 0..200 {
+        let next = tab.random_different(&mut rng);
+        assert_ne!(next, tab);
+        seen[next.index()] = true;
+        tab = next;
+    }
+    assert_eq!(seen, [true; 4]);
+}
+
+#[test]
+fn test_random_different_single() {
+    let mut rng = StdRng::seed_from_u64(7);
+    assert_eq!(Only::One.random_different(&mut rng), Only::One);
+}
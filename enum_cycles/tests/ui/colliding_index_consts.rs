@@ -0,0 +1,11 @@
+#[macro_use]
+extern crate enum_cycles_derive;
+
+#[allow(non_camel_case_types)]
+#[derive(Clone, EnumState)]
+enum Colliding {
+    FooBar,
+    Foo_bar
+}
+
+fn main() {}
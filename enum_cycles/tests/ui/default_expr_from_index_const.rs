@@ -0,0 +1,14 @@
+#[macro_use]
+extern crate enum_cycles_derive;
+
+#[derive(Clone, EnumState)]
+enum Runtime {
+    #[default(0)]
+    #[default_expr(String::from("a").len())]
+    Length(usize),
+    None
+}
+
+fn main() {
+    Runtime::from_index_const(0);
+}
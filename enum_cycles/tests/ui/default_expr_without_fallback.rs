@@ -0,0 +1,11 @@
+#[macro_use]
+extern crate enum_cycles_derive;
+
+#[derive(Clone, EnumState)]
+enum Runtime {
+    #[default_expr(String::from("a").len())]
+    Length(usize),
+    None
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#[macro_use]
+extern crate enum_cycles_derive;
+
+#[derive(Clone, EnumState)]
+enum Letters {
+    A,
+    B,
+    #[rename = "B"]
+    C
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+#[macro_use]
+extern crate enum_cycles_derive;
+
+#[derive(Clone, EnumState)]
+#[default(Numbers::One)]
+#[default(Numbers::Two)]
+enum Numbers {
+    One,
+    Two,
+    Three
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+#[macro_use]
+extern crate enum_cycles_derive;
+
+#[derive(Clone, PartialEq, Eq, EnumState)]
+#[ord(reverse)]
+enum Sizes {
+    Small,
+    Large
+}
+
+#[derive(Clone, EnumState)]
+enum Steps {
+    Start,
+    #[skip = true]
+    Hidden,
+    End
+}
+
+fn main() {}
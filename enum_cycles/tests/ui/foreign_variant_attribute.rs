@@ -0,0 +1,11 @@
+#[macro_use]
+extern crate enum_cycles_derive;
+
+#[derive(Clone, EnumState)]
+enum Shapes {
+    Point,
+    #[strum(serialize = "line")]
+    Line(u8)
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+#[macro_use]
+extern crate enum_cycles_derive;
+
+#[derive(Clone, EnumState)]
+enum Seasons {
+    #[default]
+    Spring,
+    Summer,
+    #[default]
+    Autumn,
+    Winter
+}
+
+#[derive(Clone, EnumState)]
+#[default(Directions::South)]
+enum Directions {
+    North,
+    #[default]
+    East,
+    South,
+    West
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+#[macro_use]
+extern crate enum_cycles_derive;
+
+#[derive(Clone, EnumState)]
+#[auto]
+enum Tree {
+    Leaf,
+    Node(Box<self::Tree>)
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#[macro_use]
+extern crate enum_cycles_derive;
+
+#[derive(Clone, EnumState)]
+#[by_discriminant]
+#[repr_u8]
+enum Codes {
+    Ok = 200,
+    NotFound = 404
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+#[macro_use]
+extern crate enum_cycles_derive;
+
+#[derive(Clone, EnumState)]
+#[default(Modes::Hidden)]
+enum Modes {
+    Visible,
+    #[skip]
+    Hidden
+}
+
+#[derive(Clone, EnumState)]
+enum Marked {
+    Visible,
+    #[skip]
+    #[default]
+    Hidden
+}
+
+fn main() {}
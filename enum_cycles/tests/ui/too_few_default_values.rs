@@ -0,0 +1,11 @@
+#[macro_use]
+extern crate enum_cycles_derive;
+
+#[derive(Clone, EnumState)]
+enum Pairs {
+    #[default(1)]
+    Both(u8, u8),
+    None
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+#[macro_use]
+extern crate enum_cycles_derive;
+
+#[derive(Clone, EnumState)]
+#[default(Numbers::Qux)]
+enum Numbers {
+    One,
+    Two,
+    Three
+}
+
+fn main() {}
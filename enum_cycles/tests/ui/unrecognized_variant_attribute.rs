@@ -0,0 +1,11 @@
+#[macro_use]
+extern crate enum_cycles_derive;
+
+#[derive(Clone, EnumState)]
+enum Shapes {
+    Point,
+    #[frist]
+    Line(u8)
+}
+
+fn main() {}
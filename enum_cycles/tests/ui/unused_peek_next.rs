@@ -0,0 +1,17 @@
+#![deny(unused_must_use)]
+
+#[macro_use]
+extern crate enum_cycles_derive;
+
+use enum_cycles::EnumState;
+
+#[derive(Clone, EnumState)]
+enum Letters {
+    A,
+    B,
+    C
+}
+
+fn main() {
+    Letters::A.peek_next();
+}
@@ -0,0 +1,13 @@
+#[macro_use]
+extern crate enum_cycles_derive;
+
+#[derive(Clone, EnumState)]
+#[wrap]
+#[clamp]
+enum Numbers {
+    One,
+    Two,
+    Three
+}
+
+fn main() {}
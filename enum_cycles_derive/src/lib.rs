@@ -1,4 +1,5 @@
 extern crate proc_macro;
+extern crate proc_macro2;
 extern crate quote;
 extern crate syn;
 
@@ -6,9 +7,17 @@ use self::{AttributeParseError::*, AttributeType::*};
 use proc_macro::TokenStream;
 use quote::*;
 use std::convert::TryFrom;
-use syn::export::{Span, TokenStream2};
+use std::env;
+use proc_macro2::{Literal, Span, TokenStream as TokenStream2, TokenTree};
+use syn::ext::IdentExt;
+use syn::parse::{ParseStream, Parser};
+use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{Attribute, Data, DataEnum, DeriveInput, Ident, Type, Variant};
+use syn::{parenthesized, Token};
+use syn::{Attribute, Data, DataEnum, DeriveInput, Expr, ExprLit, ExprStruct, Field, Fields, Ident, Lit, LitByteStr, Member, Meta, MetaNameValue, NestedMeta, Path, Type, Variant};
+
+#[cfg(test)]
+mod test;
 
 /// The main function used to generate an EnumState implementation.
 /// Supports four attributes: `default`, `auto`, `first`, and `last`,
@@ -25,7 +34,20 @@ use syn::{Attribute, Data, DataEnum, DeriveInput, Ident, Type, Variant};
 /// itself), it defines the default input to forward into that variant. For
 /// example, use `#[default(x)]` to indicate that `x` is the default value
 /// for the variant's fields, separated by commas. `x` must be a constant
-/// expression.
+/// expression. Variants with several fields may list their values either
+/// directly, e.g. `#[default(1, 2)]`, or as a tuple, e.g. `#[default((1, 2))]`.
+///
+/// Variants with named fields instead accept a struct literal, e.g.
+/// `#[default { x: 1, y: Inner::Right }]`, which must initialize every field.
+///
+/// Fields which refer back to the enum itself, e.g. `Node(&'static Tree)`,
+/// cannot have their values derived from their types, and must likewise be
+/// given an explicit `#[default(...)]`, such as `#[default(&Tree::Leaf)]`.
+/// Owned recursion, e.g. `Node(Box<Tree>)`, has no constant value at all, so
+/// such variants must instead be marked with `#[skip]`.
+///
+/// Alternatively, a bare `#[default]` may be placed on a single variant to
+/// mark it as the default state of the enum, in place of the top level form.
 ///
 /// e.g.
 /// ```ignore
@@ -44,6 +66,20 @@ use syn::{Attribute, Data, DataEnum, DeriveInput, Ident, Type, Variant};
 ///     }
 /// ```
 ///
+/// ### `default_expr`
+///
+/// The values given by `default` must be constant expressions, as they are
+/// used to build the enum's `values()` at compile time. When a variant's values
+/// can only be computed at runtime, e.g. `#[default_expr(Letters::last())]`,
+/// they may be given by `default_expr` instead. This is written in the same
+/// way as a variant-level `default`, but is evaluated each time the variant is
+/// constructed by `from_ordinal()`, and therefore when cycling or looking up
+/// states by index or name. As `values()`, `first()`, `last()`, and `default()`
+/// remain constant, the variant still needs a constant strategy, such as
+/// `#[first]`, `#[auto]`, or `#[default(...)]`, to use in their place. Enums
+/// with any `default_expr` do not receive an inherent `from_index_const`, as
+/// it could only construct the constant values and disagree with `from_index`.
+///
 /// ### `first`
 ///
 /// When this token is placed at the top level, it informs the compiler
@@ -63,10 +99,139 @@ use syn::{Attribute, Data, DataEnum, DeriveInput, Ident, Type, Variant};
 /// ### `auto`
 ///
 /// This is another variant of `first` and `last` which informs the compiler
-/// to try and use whichever value is specified as the default for any given
-/// field in the enum. If anywhere no value is specified as the default value,
-/// it will instead use the first value in the enum.
-#[proc_macro_derive(EnumState, attributes(default, first, last, auto))]
+/// to use the default value of each field's type, i.e. its `_DEFAULT`. For a
+/// type deriving `EnumState`, this is the value given by its own top-level
+/// `default` attribute or, when no such attribute is present, its first value.
+///
+/// Like `first` and `last`, this token may also be placed at the variant level
+/// to override whichever attribute was declared at the top level.
+///
+/// Each of `first`, `last`, and `auto` may also be placed on an individual
+/// field, e.g. `Both(#[first] Numbers, #[last] Letters)`, to override the
+/// attribute of its variant or of the enum for that field alone.
+///
+/// Fields of type `PhantomData`, e.g. in type-state tags, are always
+/// constructed as `PhantomData` and need neither an attribute nor `EnumState`.
+///
+/// ### `ord`
+///
+/// When this token is placed at the top level, it generates implementations
+/// of `PartialOrd` and `Ord` which compare states by index, i.e. in the order
+/// they are declared. The enum must also implement `Eq`. Note that two values
+/// of the same variant are considered equal in this ordering, regardless of
+/// their fields.
+///
+/// ### `hash`
+///
+/// When this token is placed at the top level, it generates an implementation
+/// of `Hash` which hashes each state by its index. Unlike `#[derive(Hash)]`,
+/// this does not require the fields of each variant to implement `Hash`. As
+/// a result, two values of the same variant with different fields will hash
+/// equally.
+///
+/// ### `try_from`
+///
+/// When this token is placed at the top level, it generates implementations
+/// of `TryFrom<usize>` and `TryFrom<&str>`, which delegate to `from_index`
+/// and `from_name` respectively. Each conversion has its own error type, named
+/// after the enum, e.g. `NumbersIndexError` and `NumbersNameError`.
+///
+/// ### `by_discriminant`
+///
+/// When this token is placed at the top level, each state's index is its
+/// declared discriminant rather than its position in the enum. Values are
+/// then sorted and cycled through in order of discriminant, skipping over any
+/// gaps between them. Each discriminant must be an integer literal, or else
+/// omitted to follow the previous one.
+///
+/// ### `start_at`
+///
+/// When this token is placed at the top level, e.g. `#[start_at(Two)]`, the
+/// given variant is treated as the first state of the enum for the purposes of
+/// cycling. Variants still follow one another in declaration order, but that
+/// order is rotated so that `values()` begins at the given variant and ends
+/// at the one declared just before it. As a result, `first()` and `last()`,
+/// as well as `#[first]` and `#[last]` on fields of this type, refer to those
+/// variants. Each variant's `index()` is offset in the same way, so that the
+/// given variant has an index of 0 and `from_index(0)` yields it. When combined
+/// with `by_discriminant`, the sorted order is rotated instead, and indices
+/// remain the declared discriminants.
+///
+/// ### `copy`
+///
+/// When this token is placed at the top level, values are copied out of the
+/// enum's constants rather than cloned when cycling between states. This is
+/// slightly cheaper in tight loops, but requires the enum to implement `Copy`.
+///
+/// ### `wrap` / `clamp`
+///
+/// These tokens select what happens when `next`, `previous`, `skip`, or
+/// `skip_backward` would pass either end of the enum. With `#[wrap]`, which
+/// is the default, states cycle around from the last value to the first and
+/// vice versa. With `#[clamp]`, they stop at the first or last value instead.
+///
+/// ### `std_default`
+///
+/// When this token is placed at the top level, an implementation of `Default`
+/// is generated for the enum which yields its default state. This is opt-in to
+/// avoid conflicting with any existing implementation. Note that calls such as
+/// `Numbers::default()` then become ambiguous and must be written as either
+/// `<Numbers as Default>::default()` or `<Numbers as EnumState>::default()`.
+///
+/// ### `repr_u8`
+///
+/// When this token is placed at the top level, inherent `to_u8` and `from_u8`
+/// functions are generated which convert between each state and its index as a
+/// `u8`. This requires every index to be less than 256.
+///
+/// ### `into_primitives`
+///
+/// When this token is placed at the top level, implementations of `From` are
+/// generated which convert each state into its index as a `usize` and its name
+/// as a `&'static str`.
+///
+/// ### `as_ref_str`
+///
+/// When this token is placed at the top level, an implementation of
+/// `AsRef<str>` is generated which yields each state's name, so that states
+/// may be passed to any function accepting `impl AsRef<str>`.
+///
+/// ### `rename`
+///
+/// When this token is placed on a variant, e.g. `#[rename = "Fall"]`, the
+/// given string is used as the variant's name in place of its identifier.
+/// This affects `name()`, `names()`, and any lookups by name.
+///
+/// ### `skip`
+///
+/// When this token is placed on a variant, that variant is excluded from the
+/// enum's values, names, and `size()`, and will never be reached by cycling.
+/// A skipped variant needs no default values for its fields. Cycling away from
+/// one behaves as though the state were the variant declared just before it,
+/// although moving by zero, e.g. `offset(0)` or `skip(0)`, leaves it in place.
+/// Use `declared_count()` to count every variant, including skipped ones.
+/// A skipped variant may not be the default state of the enum.
+///
+/// ### `enum_state`
+///
+/// When this token is placed at the top level, e.g.
+/// `#[enum_state(crate = "crate::reexports")]`, the generated code refers to
+/// the `EnumState` trait through the given path rather than by name. This is
+/// useful when the trait is re-exported from another crate or module. Paths
+/// to modules should begin with `crate` or `::`.
+///
+/// ### `cfg`
+///
+/// Variants may be gated behind `#[cfg(...)]` as usual. The compiler removes
+/// any inactive variants before this macro runs, so the generated names,
+/// values, and indices only ever include the variants which are compiled in.
+///
+/// ### `doc`
+///
+/// Doc comments on each variant are preserved and returned by `description()`.
+/// Each line is trimmed and the lines are joined by newlines. Variants without
+/// any doc comments have an empty description.
+#[proc_macro_derive(EnumState, attributes(default, default_expr, first, last, auto, ord, hash, try_from, by_discriminant, start_at, copy, wrap, clamp, skip, std_default, repr_u8, rename, into_primitives, as_ref_str, enum_state))]
 pub fn derive_enum_cycle(input: TokenStream) -> TokenStream {
     let ast: DeriveInput = syn::parse(input).unwrap();
 
@@ -74,7 +239,7 @@ pub fn derive_enum_cycle(input: TokenStream) -> TokenStream {
         if let Err(tokens) = validate_enum(&ast, e) {
             tokens
         } else {
-            debug(impl_enum_cycle(&ast, e))
+            debug(&ast, impl_enum_cycle(&ast, e))
         }
     } else {
         error(&ast.span(), "EnumState can only be derived from enum variants.")
@@ -87,19 +252,512 @@ pub fn derive_enum_cycle(input: TokenStream) -> TokenStream {
 /// some syntax errors, such as whether tokens are missing from a `default`
 /// attribute.
 fn validate_enum(ast: &DeriveInput, e: &DataEnum) -> Result<(), TokenStream2> {
+    validate_flags(ast, e)?;
     for variant in &e.variants {
-        if let Err(e) = get_attr_type(ast, &variant) {
-            if let NoneFound = e {
-                if variant.fields.is_empty() {
-                    continue;
+        get_rename(variant)?;
+    }
+    validate_unique_names(e)?;
+    for variant in e.variants.iter().filter(|v| !is_skipped(v)) {
+        validate_field_attrs(variant)?;
+        validate_default_expr(ast, variant)?;
+        match get_attr_type(ast, variant) {
+            Ok(Default(tokens)) if is_tuple(variant) => {
+                get_default_args(variant, &tokens)?;
+            }
+            Ok(Default(tokens)) => validate_default_fields(variant, &tokens)?,
+            Err(NoneFound) if variant.fields.is_empty() => (),
+            Err(NoneFound) if has_field_attrs(variant) => validate_not_recursive(ast, variant)?,
+            Err(NoneFound) => return Err(get_missing_strategy_error(variant)),
+            Err(e) => return Err(e.get_message(variant.span())),
+            Ok(_) => validate_not_recursive(ast, variant)?
+        }
+    }
+    if has_flag(&ast.attrs, "by_discriminant") {
+        get_discriminants(e)?;
+    }
+    validate_start_at(ast, e)?;
+    if e.variants.iter().all(is_skipped) {
+        return Err(error(&ast.ident.span(), "At least one variant must not be skipped."));
+    }
+    get_crate_path(ast)?;
+    validate_mode(ast)?;
+    validate_repr_u8(ast, e)?;
+    validate_index_consts(e)?;
+    validate_default(ast, e)
+}
+
+/// Ensures that no two variants share a name once `#[rename]` is applied, as
+/// lookups by name could otherwise yield either of them.
+fn validate_unique_names(e: &DataEnum) -> Result<(), TokenStream2> {
+    let rename_span = |v: &Variant| match v.attrs.iter().find(|a| a.path.is_ident("rename"))?.parse_meta() {
+        Ok(Meta::NameValue(MetaNameValue { lit, .. })) => Some(lit.span()),
+        _ => None
+    };
+    for (i, v) in e.variants.iter().enumerate() {
+        let name = get_name(v);
+        if let Some(other) = e.variants.iter().take(i).find(|o| get_name(o) == name) {
+            let span = rename_span(v).or_else(|| rename_span(other)).unwrap_or_else(|| v.ident.span());
+            let msg = format!("Variants `{}` and `{}` are both named `{}`.", other.ident, v.ident, name);
+            return Err(error(&span, &msg));
+        }
+    }
+    Ok(())
+}
+
+/// Ensures that a variant's `#[default_expr(...)]`, if any, is written in the
+/// same form as a variant-level `default`, and that the variant has some
+/// constant strategy to fall back on when building `_VALUES`.
+fn validate_default_expr(ast: &DeriveInput, variant: &Variant) -> Result<(), TokenStream2> {
+    let attr = match get_default_expr(variant) {
+        Some(attr) => attr,
+        None => return Ok(())
+    };
+    if variant.fields.is_empty() {
+        let msg = format!("Variant `{}` has no fields to supply with `default_expr`.", variant.ident);
+        return Err(error(&attr.path.span(), &msg));
+    }
+    if is_tuple(variant) {
+        get_default_args(variant, &attr.tokens)?;
+    } else {
+        validate_default_fields(variant, &attr.tokens)?;
+    }
+    if get_attr_type(ast, variant).is_err() && !has_field_attrs(variant) {
+        let msg = format!(
+            "Variant `{}` needs a constant strategy alongside `default_expr`. Expected one of `default`, `first`, `last`, or `auto`.",
+            variant.ident
+        );
+        return Err(error(&attr.path.span(), &msg));
+    }
+    Ok(())
+}
+
+/// Ensures that at most one of `#[wrap]` and `#[clamp]` is present, as these
+/// select contradictory behaviors at the ends of the enum.
+fn validate_mode(ast: &DeriveInput) -> Result<(), TokenStream2> {
+    if has_flag(&ast.attrs, "wrap") && has_flag(&ast.attrs, "clamp") {
+        let attr = ast.attrs.iter().find(|a| a.path.is_ident("clamp")).unwrap();
+        return Err(error(&attr.path.span(), "`#[wrap]` and `#[clamp]` may not be combined."));
+    }
+    Ok(())
+}
+
+/// Ensures that every index fits into a `u8`, provided the enum is annotated
+/// with `#[repr_u8]`.
+fn validate_repr_u8(ast: &DeriveInput, e: &DataEnum) -> Result<(), TokenStream2> {
+    if !has_flag(&ast.attrs, "repr_u8") {
+        return Ok(());
+    }
+    if let Some((i, v)) = get_variants(ast, e).into_iter().find(|(i, _)| *i > u8::MAX as usize) {
+        let msg = format!("Index {} of `{}` does not fit into a `u8`. Consider a wider type.", i, v.ident);
+        return Err(error(&v.ident.span(), &msg));
+    }
+    Ok(())
+}
+
+/// Ensures that the enum's default state is specified at most once, either
+/// by a top-level `#[default(...)]` or a bare `#[default]` on one variant.
+fn validate_default(ast: &DeriveInput, e: &DataEnum) -> Result<(), TokenStream2> {
+    let mut top_level = false;
+    for attr in &ast.attrs {
+        match AttributeType::try_from(attr) {
+            Ok(DefaultVariant) => return Err(MissingDefault(attr.span()).get_message(attr.span())),
+            Ok(Default(_)) if top_level => {
+                return Err(error(&attr.path.span(), "The default is already specified."));
+            }
+            Ok(Default(tokens)) => {
+                validate_default_path(ast, e, &tokens)?;
+                top_level = true;
+            }
+            _ => ()
+        }
+    }
+    let mut marked = e.variants.iter().filter(|v| is_default_variant(v));
+    if let Some(first) = marked.next() {
+        if is_skipped(first) {
+            let msg = format!("The default `{}` is marked with `#[skip]` and can never be reached.", first.ident);
+            return Err(error(&first.ident.span(), &msg));
+        }
+        if top_level {
+            let msg = "The default is already specified at the top level.";
+            return Err(error(&first.ident.span(), msg));
+        }
+        if let Some(second) = marked.next() {
+            return Err(error(&second.ident.span(), "Only one variant may be marked as `#[default]`."));
+        }
+    }
+    Ok(())
+}
+
+/// Ensures that a top-level `#[default(...)]` refers to one of the enum's
+/// variants, when written as a path through the enum itself, e.g.
+/// `Numbers::One`, `Self::One`, or `crate::nums::Numbers::One`. Only the
+/// final segment is compared against the variants. Any other paths are left
+/// to the compiler, as these may also refer to imported variants or
+/// constants. Either form is rejected when it names a variant marked with
+/// `#[skip]`, as the default would never appear in `values()`.
+fn validate_default_path(ast: &DeriveInput, e: &DataEnum, tokens: &TokenStream2) -> Result<(), TokenStream2> {
+    let path = match syn::parse2(tokens.clone()) {
+        Ok(Expr::Paren(paren)) => match *paren.expr {
+            Expr::Path(p) => p.path,
+            Expr::Call(c) => match *c.func {
+                Expr::Path(p) => p.path,
+                _ => return Ok(())
+            },
+            Expr::Struct(s) => s.path,
+            _ => return Ok(())
+        },
+        _ => return Ok(())
+    };
+    let segments: Vec<_> = path.segments.iter().map(|s| &s.ident).collect();
+    let last = segments[segments.len() - 1];
+    let through_enum = match segments.len() {
+        1 => false,
+        n => *segments[n - 2] == ast.ident || segments[n - 2] == "Self"
+    };
+    if segments.len() > 1 && !through_enum {
+        return Ok(());
+    }
+    if e.variants.iter().any(|v| v.ident == *last && is_skipped(v)) {
+        let msg = format!("The default `{}` is marked with `#[skip]` and can never be reached.", last);
+        return Err(error(&last.span(), &msg));
+    }
+    if !through_enum || e.variants.iter().any(|v| v.ident == *last) {
+        return Ok(());
+    }
+    let expected: Vec<_> = e.variants.iter().map(|v| format!("`{}`", v.ident)).collect();
+    let msg = format!("No variant `{}` in `{}`. Expected one of: {}.", last, ast.ident, expected.join(", "));
+    Err(error(&last.span(), &msg))
+}
+
+/// Determines the value of each variant's discriminant for enums annotated
+/// with `#[by_discriminant]`. These must be written as integer literals, as
+/// the variants are sorted by these values when generating the impl.
+fn get_discriminants(e: &DataEnum) -> Result<Vec<usize>, TokenStream2> {
+    let mut next = 0;
+    let mut discriminants = Vec::with_capacity(e.variants.len());
+    for variant in &e.variants {
+        if let Some((_, ref expr)) = variant.discriminant {
+            next = match expr {
+                Expr::Lit(ExprLit { lit: Lit::Int(i), .. }) => match i.base10_parse() {
+                    Ok(i) => i,
+                    Err(_) => return Err(error(&expr.span(), "Discriminant must be a non-negative integer."))
+                },
+                _ => return Err(error(&expr.span(), "Discriminant must be an integer literal."))
+            };
+        }
+        discriminants.push(next);
+        next += 1;
+    }
+    Ok(discriminants)
+}
+
+/// Produces the integer type given by the enum's `#[repr(...)]`, if any, or
+/// else `isize`, which is the default type of a discriminant.
+fn get_repr_type(ast: &DeriveInput) -> TokenStream2 {
+    const INTEGERS: &[&str] = &["u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize"];
+    for attr in ast.attrs.iter().filter(|a| a.path.is_ident("repr")) {
+        if let Ok(Meta::List(list)) = attr.parse_meta() {
+            for nested in &list.nested {
+                if let NestedMeta::Meta(Meta::Path(path)) = nested {
+                    if INTEGERS.iter().any(|i| path.is_ident(i)) {
+                        return quote!(#path);
+                    }
                 }
             }
-            return Err(e.get_message(variant.span()));
+        }
+    }
+    quote!(isize)
+}
+
+/// Produces an expression for the discriminant of each variant. Variants
+/// without an explicit discriminant are offset from the last one which has
+/// one, as the compiler does. Unlike `get_discriminants`, these may be any
+/// constant expression.
+fn get_discriminant_exprs(e: &DataEnum) -> Vec<TokenStream2> {
+    let mut base = None;
+    let mut offset = 0;
+    let mut exprs = Vec::with_capacity(e.variants.len());
+    for variant in &e.variants {
+        if let Some((_, ref expr)) = variant.discriminant {
+            base = Some(expr);
+            offset = 0;
+        }
+        let literal = Literal::usize_unsuffixed(offset);
+        exprs.push(match base {
+            Some(expr) if offset == 0 => quote!(#expr),
+            Some(expr) => quote!((#expr) + #literal),
+            None => quote!(#literal)
+        });
+        offset += 1;
+    }
+    exprs
+}
+
+/// Pairs each variant with its index, in the order in which they should be
+/// cycled through. This is declaration order, unless the enum is annotated
+/// with `#[by_discriminant]`, in which case it is sorted by discriminant.
+/// Variants marked with `#[skip]` are included in place. This order is then
+/// rotated to begin at the variant given by `#[start_at(...)]`, if any, and
+/// each index is offset by the same amount, unless it is a discriminant.
+fn get_variants<'a>(ast: &DeriveInput, e: &'a DataEnum) -> Vec<(usize, &'a Variant)> {
+    let by_discriminant = has_flag(&ast.attrs, "by_discriminant");
+    let mut variants: Vec<_> = if by_discriminant {
+        let mut variants: Vec<_> = get_discriminants(e).ok().unwrap().into_iter()
+            .zip(e.variants.iter())
+            .collect();
+        variants.sort_by_key(|(i, _)| *i);
+        variants
+    } else {
+        e.variants.iter().enumerate().collect()
+    };
+    if let Some(start) = get_start_at(ast) {
+        let position = variants.iter().position(|(_, v)| v.ident == start).unwrap();
+        variants.rotate_left(position);
+        if !by_discriminant {
+            variants = variants.into_iter().enumerate().map(|(i, (_, v))| (i, v)).collect();
+        }
+    }
+    variants
+}
+
+/// Retrieves the variant given by `#[start_at(...)]`, if any.
+fn get_start_at(ast: &DeriveInput) -> Option<Ident> {
+    let attr = ast.attrs.iter().find(|a| a.path.is_ident("start_at"))?;
+    attr.parse_args().ok()
+}
+
+/// Ensures that `#[start_at(...)]`, if present, names a single variant of
+/// the enum which is not marked with `#[skip]`.
+fn validate_start_at(ast: &DeriveInput, e: &DataEnum) -> Result<(), TokenStream2> {
+    let attr = match ast.attrs.iter().find(|a| a.path.is_ident("start_at")) {
+        Some(attr) => attr,
+        None => return Ok(())
+    };
+    let start: Ident = match attr.parse_args() {
+        Ok(start) => start,
+        Err(_) => return Err(error(&attr.path.span(), "Expected a variant, e.g. `#[start_at(Two)]`."))
+    };
+    match e.variants.iter().find(|v| v.ident == start) {
+        Some(v) if is_skipped(v) => {
+            let msg = format!("Cannot start at `{}`, as it is marked with `#[skip]`.", start);
+            Err(error(&start.span(), &msg))
+        }
+        Some(_) => Ok(()),
+        None => {
+            let expected: Vec<_> = e.variants.iter().map(|v| format!("`{}`", v.ident)).collect();
+            let msg = format!("No variant `{}` in `{}`. Expected one of: {}.", start, ast.ident, expected.join(", "));
+            Err(error(&start.span(), &msg))
+        }
+    }
+}
+
+/// Ensures that a variant-level `default` attribute on a named-field variant
+/// is written as a struct literal which initializes each of its fields.
+fn validate_default_fields(variant: &Variant, tokens: &TokenStream2) -> Result<(), TokenStream2> {
+    let fields = match variant.fields {
+        Fields::Named(ref fields) => fields,
+        _ => return Ok(())
+    };
+    let name = &variant.ident;
+    let literal: ExprStruct = match syn::parse2(quote!(#name #tokens)) {
+        Ok(literal) => literal,
+        Err(_) => return Err(error(&tokens.span(), "Expected a struct literal, e.g. `#[default { x: 1 }]`."))
+    };
+    let declared = |ident: &Ident| fields.named.iter().any(|f| f.ident.as_ref() == Some(ident));
+    for value in &literal.fields {
+        if let Member::Named(ref ident) = value.member {
+            if !declared(ident) {
+                return Err(error(&ident.span(), &format!("No field `{}` in variant `{}`.", ident, name)));
+            }
+        }
+    }
+    for field in &fields.named {
+        let ident = field.ident.as_ref().unwrap();
+        let initialized = literal.fields.iter().any(|value| match value.member {
+            Member::Named(ref m) => m == ident,
+            _ => false
+        });
+        if !initialized {
+            return Err(error(&tokens.span(), &format!("Missing field `{}` in variant `{}`.", ident, name)));
+        }
+    }
+    Ok(())
+}
+
+/// Parses the values given by a variant-level `default` attribute on a tuple
+/// variant, one per field. A single tuple is unpacked into its elements when
+/// the variant has more than one field, so that `#[default(a, b)]` and
+/// `#[default((a, b))]` are equivalent.
+fn get_default_args(variant: &Variant, tokens: &TokenStream2) -> Result<Vec<Expr>, TokenStream2> {
+    let parser = |input: ParseStream| {
+        let content;
+        parenthesized!(content in input);
+        Punctuated::<Expr, Token![,]>::parse_terminated(&content)
+    };
+    let mut args: Vec<Expr> = match parser.parse2(tokens.clone()) {
+        Ok(args) => args.into_iter().collect(),
+        Err(_) => return Err(error(&tokens.span(), "Expected a list of values, e.g. `#[default(1, 2)]`."))
+    };
+    let expected = variant.fields.len();
+    if expected > 1 && args.len() == 1 {
+        if let Expr::Tuple(ref tuple) = args[0] {
+            if tuple.elems.len() == expected {
+                args = tuple.elems.iter().cloned().collect();
+            }
+        }
+    }
+    if args.len() != expected {
+        let msg = format!("Expected {} value(s) for `{}`, found {}.", expected, variant.ident, args.len());
+        let span = args.get(expected).map_or_else(|| tokens.span(), |surplus| surplus.span());
+        return Err(error(&span, &msg));
+    }
+    Ok(args)
+}
+
+/// Determines whether the given variant has unnamed fields, e.g. `A(u8)`.
+fn is_tuple(variant: &Variant) -> bool {
+    matches!(variant.fields, Fields::Unnamed(_))
+}
+
+/// Ensures that any attributes placed on individual fields are one of
+/// `first`, `last`, or `auto`, as explicit values must be supplied for the
+/// variant as a whole.
+fn validate_field_attrs(variant: &Variant) -> Result<(), TokenStream2> {
+    for field in &variant.fields {
+        for attr in &field.attrs {
+            if let Ok(Default(_)) | Ok(DefaultVariant) = AttributeType::try_from(attr) {
+                let msg = "`default` is not supported on individual fields. Use it on the variant instead.";
+                return Err(error(&attr.path.span(), msg));
+            }
         }
     }
     Ok(())
 }
 
+/// Retrieves the attribute placed on an individual field, if any, which
+/// overrides that of its variant or the enum as a whole.
+fn get_field_attr(field: &Field) -> Option<AttributeType> {
+    match AttributeType::get_first(&field.attrs) {
+        Ok(Default(_)) | Ok(DefaultVariant) | Err(_) => None,
+        Ok(attr) => Some(attr)
+    }
+}
+
+/// Determines whether every field in the given variant has its own
+/// attribute, in which case the variant itself does not need one. Fields of
+/// type `PhantomData` need no attribute.
+fn has_field_attrs(variant: &Variant) -> bool {
+    variant.fields.iter().all(|f| is_phantom(f) || get_field_attr(f).is_some())
+}
+
+/// Determines whether the given field is a `PhantomData` marker, e.g. for
+/// type-state tags. These are always constructed as `PhantomData` and need
+/// not implement `EnumState`.
+fn is_phantom(field: &Field) -> bool {
+    match field.ty {
+        Type::Path(ref p) => p.path.segments.last().is_some_and(|s| s.ident == "PhantomData"),
+        _ => false
+    }
+}
+
+/// Every helper attribute registered by this derive.
+const ATTRIBUTES: &[&str] = &[
+    "default", "default_expr", "first", "last", "auto", "ord", "hash", "try_from", "by_discriminant",
+    "start_at", "copy", "wrap", "clamp", "skip", "std_default", "repr_u8", "rename", "into_primitives",
+    "as_ref_str", "enum_state"
+];
+
+/// Produces an error for a non-unit variant which has no way of determining
+/// its default values. When one of its attributes closely resembles one of
+/// this macro's own, e.g. `#[frist]`, it is reported as a likely typo.
+/// Attributes belonging to other derives, e.g. `#[serde(...)]`, are ignored.
+fn get_missing_strategy_error(variant: &Variant) -> TokenStream2 {
+    const EXPECTED: &str = "Expected one of `default`, `first`, `last`, or `auto`.";
+    let name = &variant.ident;
+    let typo = variant.attrs.iter().find_map(|a| {
+        let path = a.path.get_ident()?.to_string();
+        ATTRIBUTES.iter().find(|known| is_near_miss(&path, known)).map(|known| (a, path, known))
+    });
+    match typo {
+        Some((attr, path, known)) => {
+            let msg = format!(
+                "Unrecognized attribute `{}` on variant `{}`. Did you mean `{}`? {}",
+                path, name, known, EXPECTED
+            );
+            error(&attr.path.span(), &msg)
+        }
+        None => {
+            let msg = format!("No default values are defined for variant `{}`. {}", name, EXPECTED);
+            error(&name.span(), &msg)
+        }
+    }
+}
+
+/// Determines whether `name` is a likely misspelling of `known`, i.e. whether
+/// it differs by at most one edit, or two for longer names. Adjacent
+/// transpositions count as a single edit.
+fn is_near_miss(name: &str, known: &str) -> bool {
+    let max = if known.len() > 5 { 2 } else { 1 };
+    (1..=max).contains(&edit_distance(name, known))
+}
+
+/// Computes the optimal string alignment distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut d = vec![vec![0; b.len() + 1]; a.len() + 1];
+    for (i, row) in d.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    d[0] = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            d[i][j] = (d[i - 1][j] + 1).min(d[i][j - 1] + 1).min(d[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                d[i][j] = d[i][j].min(d[i - 2][j - 2] + 1);
+            }
+        }
+    }
+    d[a.len()][b.len()]
+}
+
+/// Ensures that none of the given variant's fields refer back to the enum
+/// itself, e.g. `Node(Box<Tree>)`, when their values are to be derived from
+/// their types. Such values would be infinitely recursive.
+fn validate_not_recursive(ast: &DeriveInput, variant: &Variant) -> Result<(), TokenStream2> {
+    for field in variant.fields.iter().filter(|f| !is_phantom(f)) {
+        if mentions(field.ty.to_token_stream(), &ast.ident) {
+            let msg = format!(
+                "Variant `{0}` refers to `{1}` recursively, so its value cannot be derived from its type. \
+                Mark it with `#[skip]`, or refer to a `&'static {1}` and supply it with `#[default(...)]`.",
+                variant.ident, ast.ident
+            );
+            return Err(error(&field.ty.span(), &msg));
+        }
+    }
+    Ok(())
+}
+
+/// Determines whether the given tokens contain `ident` or `Self` anywhere
+/// within them, including inside of any generic arguments. `ident` is only
+/// matched as a single-segment path, or one prefixed by exactly `self::` or
+/// `crate::`, so `other::Foo` does not mention `Foo`.
+fn mentions(tokens: TokenStream2, ident: &Ident) -> bool {
+    let tokens: Vec<TokenTree> = tokens.into_iter().collect();
+    let get = |n: Option<usize>| n.and_then(|n| tokens.get(n));
+    let is_colon = |t: Option<&TokenTree>| matches!(t, Some(TokenTree::Punct(p)) if p.as_char() == ':');
+    let is_local = |t: Option<&TokenTree>| matches!(t, Some(TokenTree::Ident(i)) if i == "self" || i == "crate");
+    tokens.iter().enumerate().any(|(n, token)| match token {
+        TokenTree::Ident(i) if i == "Self" => true,
+        TokenTree::Ident(i) if i == ident && !is_colon(tokens.get(n + 1)) => {
+            !is_colon(get(n.checked_sub(1))) || is_local(get(n.checked_sub(3))) && !is_colon(get(n.checked_sub(4)))
+        }
+        TokenTree::Group(g) => mentions(g.stream(), ident),
+        _ => false
+    })
+}
+
 /// Attempts to retrieve the type of attribute specified for the given variant.
 /// It first searches for any attribute specified at the variant level, and then
 /// subsequently at the top level of the enum, if nothing is found. The top level
@@ -112,7 +770,7 @@ fn get_attr_type(ast: &DeriveInput, v: &Variant) -> Result<AttributeType, Attrib
     }
     for attr in &ast.attrs {
         match AttributeType::try_from(attr) {
-            Err(NoneFound) | Ok(Default(_)) => (),
+            Err(NoneFound) | Ok(Default(_)) | Ok(DefaultVariant) => (),
             r => return r
         }
     }
@@ -120,32 +778,98 @@ fn get_attr_type(ast: &DeriveInput, v: &Variant) -> Result<AttributeType, Attrib
 }
 
 /// Counterpart to `self::get_attr_type` which can retrieve only a `default` token
-/// at the top level of the enum.
-fn get_default(ast: &DeriveInput, default: &TokenStream2) -> TokenStream2 {
+/// at the top level of the enum, or else the value of the variant marked with a
+/// bare `#[default]`.
+fn get_default(ast: &DeriveInput, variants: &[(usize, &Variant)], values: &[TokenStream2]) -> TokenStream2 {
     for attr in &ast.attrs {
         if let Ok(Default(tokens)) = AttributeType::try_from(attr) {
             return tokens;
         }
     }
-    default.clone()
+    match variants.iter().position(|(_, v)| is_default_variant(v)) {
+        Some(ordinal) => values[ordinal].clone(),
+        None => values[0].clone()
+    }
+}
+
+/// Retrieves the name given to the variant by `#[rename = "..."]`, if any.
+fn get_rename(v: &Variant) -> Result<Option<String>, TokenStream2> {
+    let attr = match v.attrs.iter().find(|a| a.path.is_ident("rename")) {
+        Some(attr) => attr,
+        None => return Ok(None)
+    };
+    match attr.parse_meta() {
+        Ok(Meta::NameValue(MetaNameValue { lit: Lit::Str(ref s), .. })) => Ok(Some(s.value())),
+        _ => Err(error(&attr.path.span(), "Expected a string, e.g. `#[rename = \"Name\"]`."))
+    }
+}
+
+/// Determines the name of the given variant, as it appears in `names()`.
+/// Raw identifiers are stripped of their `r#` prefix.
+fn get_name(v: &Variant) -> String {
+    get_rename(v).ok().unwrap().unwrap_or_else(|| v.ident.unraw().to_string())
+}
+
+/// Retrieves the `#[default_expr(...)]` attribute on the given variant, if any.
+fn get_default_expr(v: &Variant) -> Option<&Attribute> {
+    v.attrs.iter().find(|a| a.path.is_ident("default_expr"))
+}
+
+/// Determines whether the given variant is marked with `#[skip]`, excluding
+/// it from the values which can be cycled through.
+fn is_skipped(v: &Variant) -> bool {
+    has_flag(&v.attrs, "skip")
+}
+
+/// Determines whether the given variant is marked with a bare `#[default]`.
+fn is_default_variant(v: &Variant) -> bool {
+    v.attrs.iter().any(|a| matches!(AttributeType::try_from(a), Ok(DefaultVariant)))
 }
 
 fn impl_enum_cycle(ast: &DeriveInput, e: &DataEnum) -> TokenStream2 {
-    let (names, values) = get_arrays(ast, e);
+    let trait_path = get_trait_path(ast);
+    let declared = get_variants(ast, e);
+    let variants: Vec<_> = declared.iter()
+        .filter(|(_, v)| !is_skipped(v))
+        .copied()
+        .collect();
+    let (names, values) = get_arrays(ast, &variants);
     let (first, last) = get_ends(&values);
-    let (index_map, name_map) = get_maps(ast, e);
-    let default = get_default(ast, &first);
+    let (index_map, name_map) = get_maps(ast, &declared);
+    let description_map = get_description_map(ast, &declared);
+    let by_index = get_by_index(ast, &declared);
+    let skipped = get_skipped_impl(ast, &declared, &variants);
+    let deep = get_deep_impl(ast, &declared, &variants);
+    let copy = get_copy_impl(ast, &variants);
+    let default_expr = get_default_expr_impl(ast, &variants);
+    let clamp = get_clamp_impl(ast);
+    let offset = get_offset_impl(ast, &variants, &values);
+    let from_name = get_from_name_impl(ast, &names);
+    let default = get_default(ast, &variants, &values);
+    let allow = get_allow_deprecated(ast, e);
+    let ord = get_ord_impl(ast);
+    let hash = get_hash_impl(ast);
+    let std_default = get_std_default_impl(ast);
+    let repr_u8 = get_repr_u8_impl(ast, e);
+    let into_primitives = get_into_primitives_impl(ast);
+    let as_ref_str = get_as_ref_str_impl(ast);
+    let try_from = get_try_from_impl(ast);
+    let inherent = get_inherent_impl(ast, e, &variants, &names, &values);
+    let indices: Vec<_> = variants.iter().map(|(i, _)| i).collect();
     let name = &ast.ident;
-    let size = e.variants.len();
+    let size = variants.len();
 
     quote! {
-        impl EnumState for #name {
+        #allow
+        impl #trait_path for #name {
             const _NAMES: &'static [&'static str] = &[#(#names),*];
             const _VALUES: &'static [Self] = &[#(#values),*];
             const _DEFAULT: Self = #default;
             const _FIRST: Self = #first;
             const _LAST: Self = #last;
             const _SIZE: usize = #size;
+            const _PAIRS: &'static [(usize, &'static str)] = &[#((#indices, #names)),*];
+            const _INDICES: &'static [usize] = &[#(#indices),*];
 
             fn index(&self) -> usize {
                 match *self {
@@ -158,18 +882,238 @@ fn impl_enum_cycle(ast: &DeriveInput, e: &DataEnum) -> TokenStream2 {
                     #name_map
                 }
             }
+
+            fn description(&self) -> &'static str {
+                match *self {
+                    #description_map
+                }
+            }
+
+            #by_index
+            #skipped
+            #deep
+            #copy
+            #default_expr
+            #clamp
+            #offset
+            #from_name
         }
+
+        #inherent
+        #ord
+        #hash
+        #std_default
+        #repr_u8
+        #into_primitives
+        #as_ref_str
+        #try_from
+    }
+}
+
+/// Produces an inherent impl block for any associated items which should be
+/// reachable without importing `EnumState`. This currently includes the
+/// `ALL`, `VARIANTS`, and `VARIANT_INDICES` arrays, the `NAMES_JOINED`
+/// string, the per-variant `*_INDEX` constants, `min` and `max`, which are
+/// inherent so as not to clash with `Ord`, the `const fn discriminant`,
+/// the `const fn name_index`, and the `const fn from_index_const`, which
+/// constructs each value directly rather than cloning it out of `_VALUES`,
+/// and therefore does not require `Copy`. The latter is omitted when any
+/// variant has a `#[default_expr(...)]`, as this cannot be evaluated in a
+/// `const` context.
+fn get_inherent_impl(ast: &DeriveInput, e: &DataEnum, variants: &[(usize, &Variant)], names: &[String], values: &[TokenStream2]) -> TokenStream2 {
+    let trait_path = get_trait_path(ast);
+    let allow = get_allow_deprecated(ast, e);
+    let parent = &ast.ident;
+    let joined = names.join(", ");
+    let from_index_const = if variants.iter().any(|(_, v)| get_default_expr(v).is_some()) {
+        TokenStream2::new()
+    } else {
+        let arms = variants.iter().zip(values).map(|((i, _), value)| {
+            quote!(#i => ::core::option::Option::Some(#value),)
+        });
+        quote! {
+            /// Retrieves the variant at the given index in a `const` context.
+            pub const fn from_index_const(i: usize) -> ::core::option::Option<Self> {
+                match i {
+                    #(#arms)*
+                    _ => ::core::option::Option::None
+                }
+            }
+        }
+    };
+    let index_consts = get_variants(ast, e).into_iter().map(|(i, v)| {
+        let constant = get_index_const(v);
+        let doc = format!("The index of `{}::{}`.", parent, v.ident.unraw());
+        quote! {
+            #[doc = #doc]
+            pub const #constant: usize = #i;
+        }
+    });
+    let repr = get_repr_type(ast);
+    let discriminant_arms: TokenStream2 = e.variants.iter()
+        .zip(get_discriminant_exprs(e))
+        .map(|(v, d)| get_map(v, parent, d))
+        .collect();
+    let name_arms = variants.iter().zip(names).map(|((i, _), name)| {
+        let bytes = LitByteStr::new(name.as_bytes(), Span::call_site());
+        quote!(#bytes => ::core::option::Option::Some(#i),)
+    });
+
+    quote! {
+        #allow
+        #[allow(dead_code)]
+        impl #parent {
+            /// The default value of each variant, exactly as in
+            /// `EnumState::values()`.
+            pub const ALL: &'static [Self] = <Self as #trait_path>::_VALUES;
+
+            /// The name of each variant, exactly as in `EnumState::names()`.
+            pub const VARIANTS: &'static [&'static str] = <Self as #trait_path>::_NAMES;
+
+            /// The index of each variant, exactly as in `EnumState::indices()`.
+            pub const VARIANT_INDICES: &'static [usize] = <Self as #trait_path>::_INDICES;
+
+            /// The name of each variant, separated by commas, e.g. for use in
+            /// error messages.
+            pub const NAMES_JOINED: &'static str = #joined;
+
+            #(#index_consts)*
+
+            /// Yields the lowest value in the enum, i.e. `EnumState::first()`.
+            /// Being inherent, this is not ambiguous with `Ord::min`.
+            #[must_use]
+            pub fn min() -> Self {
+                <Self as #trait_path>::first()
+            }
+
+            /// Yields the highest value in the enum, i.e. `EnumState::last()`.
+            /// Being inherent, this is not ambiguous with `Ord::max`.
+            #[must_use]
+            pub fn max() -> Self {
+                <Self as #trait_path>::last()
+            }
+
+            /// Determines the declared discriminant of the current state, as
+            /// the type given by `#[repr(...)]`, or else `isize`. Unlike
+            /// `index()`, this ignores `#[by_discriminant]`, `#[start_at]`,
+            /// and `#[skip]`.
+            pub const fn discriminant(&self) -> #repr {
+                match *self {
+                    #discriminant_arms
+                }
+            }
+
+            /// Determines the index of the variant with the given name in a
+            /// `const` context.
+            pub const fn name_index(name: &[u8]) -> ::core::option::Option<usize> {
+                match name {
+                    #(#name_arms)*
+                    _ => ::core::option::Option::None
+                }
+            }
+
+            #from_index_const
+        }
+    }
+}
+
+/// Produces the name of the constant holding the given variant's index,
+/// e.g. `ZERO_INDEX` for `Zero`.
+fn get_index_const(v: &Variant) -> Ident {
+    format_ident!("{}_INDEX", to_snake_case(&v.ident.unraw().to_string()).to_uppercase())
+}
+
+/// Ensures that no two variants produce the same `*_INDEX` constant, e.g.
+/// `FooBar` and `Foo_bar`.
+fn validate_index_consts(e: &DataEnum) -> Result<(), TokenStream2> {
+    for (i, v) in e.variants.iter().enumerate() {
+        let constant = get_index_const(v);
+        if let Some(other) = e.variants.iter().take(i).find(|o| get_index_const(o) == constant) {
+            let msg = format!(
+                "Variants `{}` and `{}` would both produce the constant `{}`.",
+                other.ident, v.ident, constant
+            );
+            return Err(error(&v.ident.span(), &msg));
+        }
+    }
+    Ok(())
+}
+
+/// Produces implementations of `PartialOrd` and `Ord` which compare states
+/// by index, provided the enum is annotated with `#[ord]`.
+fn get_ord_impl(ast: &DeriveInput) -> TokenStream2 {
+    if !has_flag(&ast.attrs, "ord") {
+        return TokenStream2::new();
+    }
+    let trait_path = get_trait_path(ast);
+    let name = &ast.ident;
+
+    quote! {
+        impl ::core::cmp::PartialOrd for #name {
+            fn partial_cmp(&self, other: &Self) -> ::core::option::Option<::core::cmp::Ordering> {
+                ::core::option::Option::Some(::core::cmp::Ord::cmp(self, other))
+            }
+        }
+
+        impl ::core::cmp::Ord for #name {
+            fn cmp(&self, other: &Self) -> ::core::cmp::Ordering {
+                ::core::cmp::Ord::cmp(&#trait_path::index(self), &#trait_path::index(other))
+            }
+        }
+    }
+}
+
+/// Retrieves the crate path given by `#[enum_state(crate = "...")]`, if any.
+fn get_crate_path(ast: &DeriveInput) -> Result<Option<Path>, TokenStream2> {
+    let attr = match ast.attrs.iter().find(|a| a.path.is_ident("enum_state")) {
+        Some(attr) => attr,
+        None => return Ok(None)
+    };
+    let msg = "Expected a crate path, e.g. `#[enum_state(crate = \"my_crate\")]`.";
+    let list = match attr.parse_meta() {
+        Ok(Meta::List(list)) => list,
+        _ => return Err(error(&attr.path.span(), msg))
+    };
+    for nested in &list.nested {
+        if let NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit: Lit::Str(s), .. })) = nested {
+            if path.is_ident("crate") {
+                return s.parse().map(Some).map_err(|_| error(&s.span(), msg));
+            }
+        }
+    }
+    Err(error(&attr.path.span(), msg))
+}
+
+/// Produces the fully-qualified path to the `EnumState` trait to be used by
+/// the generated code, so that it does not need to be in scope at the call
+/// site. This is relative to the crate given by `#[enum_state(crate = "...")]`
+/// when present, or else `::enum_cycles`.
+fn get_trait_path(ast: &DeriveInput) -> TokenStream2 {
+    match get_crate_path(ast).ok().unwrap() {
+        Some(path) => quote!(#path::EnumState),
+        None => quote!(::enum_cycles::EnumState)
+    }
+}
+
+/// Produces an `#[allow(deprecated)]` attribute when the enum or any of its
+/// variants is deprecated, as the generated code must refer to each of them.
+fn get_allow_deprecated(ast: &DeriveInput, e: &DataEnum) -> TokenStream2 {
+    let deprecated = |attrs: &[Attribute]| attrs.iter().any(|a| a.path.is_ident("deprecated"));
+    if deprecated(&ast.attrs) || e.variants.iter().any(|v| deprecated(&v.attrs)) {
+        quote!(#[allow(deprecated)])
+    } else {
+        TokenStream2::new()
     }
 }
 
 // Moving some code outside of `impl_enum_cycle`. Hopefully, this makes it
 // easier to read.
-fn get_arrays(ast: &DeriveInput, e: &DataEnum) -> (Vec<String>, Vec<TokenStream2>) {
-    let names = e.variants.iter()
-        .map(|v| v.ident.to_string())
+fn get_arrays(ast: &DeriveInput, variants: &[(usize, &Variant)]) -> (Vec<String>, Vec<TokenStream2>) {
+    let names = variants.iter()
+        .map(|(_, v)| get_name(v))
         .collect();
-    let values = e.variants.iter()
-        .map(|v| get_constructor(ast, v))
+    let values = variants.iter()
+        .map(|(_, v)| get_constructor(ast, v))
         .collect();
     (names, values)
 }
@@ -177,53 +1121,395 @@ fn get_arrays(ast: &DeriveInput, e: &DataEnum) -> (Vec<String>, Vec<TokenStream2
 /// Produces the necessary tokens for constructing a new variant based on
 /// its annotations.
 fn get_constructor(ast: &DeriveInput, variant: &Variant) -> TokenStream2 {
+    let trait_path = get_trait_path(ast);
     let parent = &ast.ident;
     let name = &variant.ident;
 
     if variant.fields.is_empty() {
         return quote!(#parent::#name);
     }
-    let attr = match get_attr_type(ast, variant).ok().unwrap() {
-        Default(tokens) => return quote!(#parent::#name#tokens),
-        a => a
+    let attr = match get_attr_type(ast, variant) {
+        Ok(Default(tokens)) => return get_explicit_constructor(ast, variant, &tokens),
+        attr => attr.ok()
     };
     let fields: TokenStream2 = variant.fields.iter()
-        .map(|f| get_constant(&f.ty, &attr))
+        .map(|f| {
+            let constant = match get_field_attr(f) {
+                _ if is_phantom(f) => quote!(::core::marker::PhantomData,),
+                Some(field_attr) => get_constant(&f.ty, &field_attr, &trait_path),
+                None => get_constant(&f.ty, attr.as_ref().unwrap(), &trait_path)
+            };
+            match f.ident {
+                Some(ref ident) => quote!(#ident: #constant),
+                None => constant
+            }
+        })
         .collect();
-    quote!(#parent::#name(#fields))
+    match variant.fields {
+        Fields::Named(_) => quote!(#parent::#name { #fields }),
+        _ => quote!(#parent::#name(#fields))
+    }
+}
+
+/// Produces the tokens for constructing a variant from the values given in
+/// a variant-level `default` or `default_expr` attribute.
+fn get_explicit_constructor(ast: &DeriveInput, variant: &Variant, tokens: &TokenStream2) -> TokenStream2 {
+    let parent = &ast.ident;
+    let name = &variant.ident;
+    if is_tuple(variant) {
+        let args = get_default_args(variant, tokens).ok().unwrap();
+        quote!(#parent::#name(#(#args),*))
+    } else {
+        quote!(#parent::#name#tokens)
+    }
 }
 
 /// Determines which constant to use for the default value to use in each
 /// field in a variant based on its annotations, assuming the constructor
-/// has not been explicitly defined.
-fn get_constant(f_ty: &Type, attr: &AttributeType) -> TokenStream2 {
+/// has not been explicitly defined. Note that `auto` simply uses the field's
+/// `_DEFAULT`, as this constant already resolves to the field's first value
+/// when its type does not specify a default.
+fn get_constant(f_ty: &Type, attr: &AttributeType, trait_path: &TokenStream2) -> TokenStream2 {
     match attr {
-        First => quote!(<#f_ty>::_FIRST,),
-        Last => quote!(<#f_ty>::_LAST,),
-        _ => quote!(<#f_ty>::_DEFAULT,)
+        First => quote!(<#f_ty as #trait_path>::_FIRST,),
+        Last => quote!(<#f_ty as #trait_path>::_LAST,),
+        Auto => quote!(<#f_ty as #trait_path>::_DEFAULT,),
+        Default(_) | DefaultVariant => unreachable!("Explicit defaults are handled by get_constructor.")
     }
 }
 
-fn get_ends(vec: &Vec<TokenStream2>) -> (TokenStream2, TokenStream2) {
+fn get_ends(vec: &[TokenStream2]) -> (TokenStream2, TokenStream2) {
     (vec.first().unwrap().clone(), vec.last().unwrap().clone())
 }
 
-fn get_maps(ast: &DeriveInput, e: &DataEnum) -> (TokenStream2, TokenStream2) {
-    (get_index_map(ast, e), get_name_map(ast, e))
+fn get_maps(ast: &DeriveInput, variants: &[(usize, &Variant)]) -> (TokenStream2, TokenStream2) {
+    (get_index_map(ast, variants), get_name_map(ast, variants))
 }
 
-fn get_index_map(ast: &DeriveInput, e: &DataEnum) -> TokenStream2 {
-    e.variants.iter().enumerate()
+fn get_index_map(ast: &DeriveInput, variants: &[(usize, &Variant)]) -> TokenStream2 {
+    variants.iter()
         .map(|(i, v)| get_map(v, &ast.ident, i))
         .collect()
 }
 
-fn get_name_map(ast: &DeriveInput, e: &DataEnum) -> TokenStream2 {
-    e.variants.iter()
-        .map(|v| get_map(v, &ast.ident, v.ident.to_string()))
+fn get_name_map(ast: &DeriveInput, variants: &[(usize, &Variant)]) -> TokenStream2 {
+    variants.iter()
+        .map(|(_, v)| get_map(v, &ast.ident, get_name(v)))
         .collect()
 }
 
+fn get_description_map(ast: &DeriveInput, variants: &[(usize, &Variant)]) -> TokenStream2 {
+    variants.iter()
+        .map(|(_, v)| get_map(v, &ast.ident, get_description(v)))
+        .collect()
+}
+
+/// Joins each line of the doc comments on the given variant, trimming them
+/// of the whitespace which follows `///`.
+fn get_description(v: &Variant) -> String {
+    v.attrs.iter()
+        .filter(|a| a.path.is_ident("doc"))
+        .filter_map(|a| match a.parse_meta() {
+            Ok(Meta::NameValue(MetaNameValue { lit: Lit::Str(s), .. })) => Some(s.value()),
+            _ => None
+        })
+        .flat_map(|s| s.split('\n').map(|l| l.trim().to_string()).collect::<Vec<_>>())
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// Produces overrides of `ordinal` and `from_index` for enums whose indices
+/// are not simply their positions in `values()`. Skipped variants share the
+/// ordinal of the variant before them, wrapping around to the last, so that
+/// cycling away from them behaves as though they were that variant.
+fn get_by_index(ast: &DeriveInput, declared: &[(usize, &Variant)]) -> TokenStream2 {
+    let trait_path = get_trait_path(ast);
+    let size = declared.iter().filter(|(_, v)| !is_skipped(v)).count();
+    let mut ordinals = Vec::with_capacity(declared.len());
+    let mut next = 0usize;
+    for (_, v) in declared {
+        if is_skipped(v) {
+            ordinals.push(next.checked_sub(1).unwrap_or(size - 1));
+        } else {
+            ordinals.push(next);
+            next += 1;
+        }
+    }
+    if declared.iter().zip(&ordinals).all(|((i, _), ordinal)| ordinal == i) {
+        return TokenStream2::new();
+    }
+    let ordinal_map: TokenStream2 = declared.iter().zip(&ordinals)
+        .map(|((_, v), ordinal)| get_map(v, &ast.ident, ordinal))
+        .collect();
+    let index_arms = declared.iter().zip(&ordinals)
+        .filter(|((_, v), _)| !is_skipped(v))
+        .map(|((i, _), ordinal)| quote!(#i => <Self as #trait_path>::from_ordinal(#ordinal),));
+
+    quote! {
+        fn ordinal(&self) -> usize {
+            match *self {
+                #ordinal_map
+            }
+        }
+
+        fn from_index(i: usize) -> ::core::option::Option<Self> {
+            match i {
+                #(#index_arms)*
+                _ => ::core::option::Option::None
+            }
+        }
+    }
+}
+
+/// Produces overrides of `declared_count` and `is_leading_skip` for enums
+/// containing variants marked with `#[skip]`, as these are not included in
+/// `size()`.
+fn get_skipped_impl(ast: &DeriveInput, declared: &[(usize, &Variant)], variants: &[(usize, &Variant)]) -> TokenStream2 {
+    if declared.len() == variants.len() {
+        return TokenStream2::new();
+    }
+    let count = declared.len();
+    let leading: TokenStream2 = declared.iter()
+        .take_while(|(_, v)| is_skipped(v))
+        .map(|(_, v)| get_map(v, &ast.ident, true))
+        .collect();
+    let leading_impl = if leading.is_empty() {
+        TokenStream2::new()
+    } else {
+        quote! {
+            fn is_leading_skip(&self) -> bool {
+                match *self {
+                    #leading
+                    _ => false
+                }
+            }
+        }
+    };
+    quote! {
+        fn declared_count() -> usize {
+            #count
+        }
+
+        #leading_impl
+    }
+}
+
+/// Produces an override of `from_ordinal` which copies values out of
+/// `_VALUES` instead of cloning them, provided the enum is annotated with
+/// `#[copy]`. This requires the enum to implement `Copy`.
+fn get_copy_impl(ast: &DeriveInput, variants: &[(usize, &Variant)]) -> TokenStream2 {
+    // `from_ordinal` is instead produced by `get_default_expr_impl`.
+    if !has_flag(&ast.attrs, "copy") || variants.iter().any(|(_, v)| get_default_expr(v).is_some()) {
+        return TokenStream2::new();
+    }
+    let trait_path = get_trait_path(ast);
+    quote! {
+        fn from_ordinal(i: usize) -> ::core::option::Option<Self> {
+            <Self as #trait_path>::_VALUES.get(i).copied()
+        }
+    }
+}
+
+/// Produces an override of `from_ordinal` which evaluates the values given
+/// by any `#[default_expr(...)]` at runtime, rather than cloning the constant
+/// fallback out of `_VALUES`.
+fn get_default_expr_impl(ast: &DeriveInput, variants: &[(usize, &Variant)]) -> TokenStream2 {
+    let arms: Vec<_> = variants.iter()
+        .enumerate()
+        .filter_map(|(ordinal, (_, v))| {
+            let attr = get_default_expr(v)?;
+            let constructor = get_explicit_constructor(ast, v, &attr.tokens);
+            Some(quote!(#ordinal => ::core::option::Option::Some(#constructor),))
+        })
+        .collect();
+    if arms.is_empty() {
+        return TokenStream2::new();
+    }
+    let trait_path = get_trait_path(ast);
+    quote! {
+        fn from_ordinal(i: usize) -> ::core::option::Option<Self> {
+            match i {
+                #(#arms)*
+                _ => <Self as #trait_path>::_VALUES.get(i).cloned()
+            }
+        }
+    }
+}
+
+/// Produces overrides of `skip` and `skip_backward` which saturate at either
+/// end of the enum, provided the enum is annotated with `#[clamp]`. Wrapping
+/// is already the default behavior and needs no override.
+fn get_clamp_impl(ast: &DeriveInput) -> TokenStream2 {
+    if !has_flag(&ast.attrs, "clamp") {
+        return TokenStream2::new();
+    }
+    let trait_path = get_trait_path(ast);
+    quote! {
+        fn skip(&mut self, num: usize) {
+            <Self as #trait_path>::saturating_skip(self, num)
+        }
+
+        fn skip_backward(&mut self, num: usize) {
+            <Self as #trait_path>::saturating_skip_backward(self, num)
+        }
+    }
+}
+
+/// Produces an override of `offset` which constructs the resulting value with
+/// a single match on its ordinal, allowing the whole step to be inlined. This
+/// is omitted when any variant has a `#[default_expr(...)]`, as `offset` must
+/// then go through `from_ordinal` to evaluate it.
+fn get_offset_impl(ast: &DeriveInput, variants: &[(usize, &Variant)], values: &[TokenStream2]) -> TokenStream2 {
+    if variants.iter().any(|(_, v)| get_default_expr(v).is_some()) {
+        return TokenStream2::new();
+    }
+    let trait_path = get_trait_path(ast);
+    let arms = values.iter().enumerate().map(|(ordinal, value)| quote!(#ordinal => #value,));
+
+    quote! {
+        fn offset(&self, delta: isize) -> Self {
+            if delta == 0 {
+                return ::core::clone::Clone::clone(self);
+            }
+            match <Self as #trait_path>::ordinal_wrapping_offset(self, delta) {
+                #(#arms)*
+                _ => ::core::unreachable!()
+            }
+        }
+    }
+}
+
+/// Produces an override of `from_name` which performs a binary search over
+/// a table of names sorted at compile time, rather than a linear scan.
+fn get_from_name_impl(ast: &DeriveInput, names: &[String]) -> TokenStream2 {
+    let trait_path = get_trait_path(ast);
+    let mut sorted: Vec<_> = names.iter().enumerate().collect();
+    sorted.sort_by_key(|(_, name)| *name);
+    let entries = sorted.iter().map(|(ordinal, name)| quote!((#name, #ordinal)));
+
+    quote! {
+        fn from_name(name: &str) -> ::core::option::Option<Self> {
+            const SORTED: &[(&str, usize)] = &[#(#entries),*];
+            let i = SORTED.binary_search_by(|(n, _)| (*n).cmp(name)).ok()?;
+            <Self as #trait_path>::from_ordinal(SORTED[i].1)
+        }
+    }
+}
+
+/// Produces overrides of `step_inner`, `deep_from_ordinal`, and
+/// `total_states` for enums containing nested states, enabling `deep_next`
+/// and `deep_previous` to traverse them.
+fn get_deep_impl(ast: &DeriveInput, declared: &[(usize, &Variant)], variants: &[(usize, &Variant)]) -> TokenStream2 {
+    let trait_path = get_trait_path(ast);
+    let parent = &ast.ident;
+    let nested: Vec<_> = variants.iter().enumerate()
+        .filter(|(_, (_, v))| is_nested(ast, v))
+        .collect();
+    if nested.is_empty() {
+        return TokenStream2::new();
+    }
+    let step_arms = nested.iter().map(|(_, (_, v))| {
+        let name = &v.ident;
+        let bindings: Vec<_> = v.fields.iter().enumerate()
+            .filter(|(_, f)| !is_phantom(f))
+            .map(|(i, f)| f.ident.clone().unwrap_or_else(|| format_ident!("f{}", i)))
+            .collect();
+        let patterns = v.fields.iter().enumerate().map(|(i, f)| match f.ident {
+            Some(ref ident) if is_phantom(f) => quote!(#ident: _),
+            Some(ref ident) => quote!(ref mut #ident),
+            None if is_phantom(f) => quote!(_),
+            None => {
+                let binding = format_ident!("f{}", i);
+                quote!(ref mut #binding)
+            }
+        });
+        let pattern = match v.fields {
+            Fields::Named(_) => quote!(#parent::#name { #(#patterns),* }),
+            _ => quote!(#parent::#name(#(#patterns),*))
+        };
+        // Step the last field first, like an odometer.
+        let steps = bindings.iter().rev().map(|b| quote!(#trait_path::deep_step(#b, forward)));
+        quote!(#pattern => #(#steps)&&*,)
+    });
+    let wildcard = if nested.len() < declared.len() {
+        quote!(_ => true)
+    } else {
+        TokenStream2::new()
+    };
+    let ordinal_arms = nested.iter().map(|(ordinal, (_, v))| {
+        let name = &v.ident;
+        let fields: TokenStream2 = v.fields.iter()
+            .map(|f| {
+                let ty = &f.ty;
+                let end = if is_phantom(f) {
+                    quote!(::core::marker::PhantomData)
+                } else {
+                    quote! {
+                        if forward { <#ty as #trait_path>::deep_first() } else { <#ty as #trait_path>::deep_last() }
+                    }
+                };
+                match f.ident {
+                    Some(ref ident) => quote!(#ident: #end,),
+                    None => quote!(#end,)
+                }
+            })
+            .collect();
+        let constructor = match v.fields {
+            Fields::Named(_) => quote!(#parent::#name { #fields }),
+            _ => quote!(#parent::#name(#fields))
+        };
+        quote!(#ordinal => ::core::option::Option::Some(#constructor),)
+    });
+
+    let totals = variants.iter().map(|(_, v)| {
+        if is_nested(ast, v) {
+            let factors = v.fields.iter().filter(|f| !is_phantom(f)).map(|f| {
+                let ty = &f.ty;
+                quote!(<#ty as #trait_path>::total_states())
+            });
+            quote!((1 #(* #factors)*))
+        } else {
+            quote!(1)
+        }
+    });
+
+    quote! {
+        fn total_states() -> usize {
+            0 #(+ #totals)*
+        }
+
+        fn step_inner(&mut self, forward: bool) -> bool {
+            match *self {
+                #(#step_arms)*
+                #wildcard
+            }
+        }
+
+        fn deep_from_ordinal(i: usize, forward: bool) -> ::core::option::Option<Self> {
+            match i {
+                #(#ordinal_arms)*
+                _ => <Self as #trait_path>::from_ordinal(i)
+            }
+        }
+    }
+}
+
+/// Determines whether the given variant contains nested states which can be
+/// traversed by `deep_next`. This is true for any variant whose fields are
+/// derived from their types' own constants, as these must implement
+/// `EnumState`. Fields constructed via `default` are treated as opaque, as
+/// are `PhantomData` markers.
+fn is_nested(ast: &DeriveInput, v: &Variant) -> bool {
+    let has_states = !v.fields.iter().all(is_phantom);
+    match get_attr_type(ast, v) {
+        Ok(Default(_)) => false,
+        Err(_) => has_states && has_field_attrs(v),
+        Ok(_) => has_states
+    }
+}
+
 /// Produces a match arm which will ignore any fields for the given variant,
 /// yielding `t` as the branch.
 fn get_map(v: &Variant, parent: &Ident, t: impl ToTokens) -> TokenStream2 {
@@ -231,14 +1517,15 @@ fn get_map(v: &Variant, parent: &Ident, t: impl ToTokens) -> TokenStream2 {
     if v.fields.is_empty() {
         quote!(#parent::#name => #t,)
     } else {
-        let f = v.fields.iter().map(|_| quote!(_));
-        quote!(#parent::#name(#(#f),*) => #t,)
+        quote!(#parent::#name { .. } => #t,)
     }
 }
 
 /// The list of attributes supported by the macro.
 enum AttributeType {
     Default(TokenStream2),
+    // A bare `#[default]`, marking a variant as the enum's default.
+    DefaultVariant,
     Auto,
     First,
     Last
@@ -251,7 +1538,7 @@ impl AttributeType {
     fn get_first(attrs: &Vec<Attribute>) -> Result<AttributeType, AttributeParseError> {
         for attr in attrs {
             match Self::try_from(attr) {
-                Err(NoneFound) => (),
+                Err(NoneFound) | Ok(DefaultVariant) => (),
                 r => return r
             }
         }
@@ -264,34 +1551,49 @@ impl TryFrom<&Attribute> for AttributeType {
 
     /// Attempts to parse the input attribute as one of the attributes supported
     /// by the macro. May return either a value, a syntax error, or simply
-    /// `NoneFound`.
+    /// `NoneFound`. The shape of each attribute is determined by `parse_meta`,
+    /// with the exception of `default`, whose arguments are expressions rather
+    /// than literals and are therefore forwarded as is.
     fn try_from(attr: &Attribute) -> Result<AttributeType, AttributeParseError> {
         let path = match attr.path.get_ident() {
             None => return Err(InvalidPath(attr.span())),
             Some(p) => p,
         };
-        return match path.to_string().as_ref() {
-            "default" => {
-                if attr.tokens.is_empty() {
-                    Err(MissingDefault(attr.span()))
-                } else {
-                    Ok(Default(attr.tokens.clone()))
-                }
-            }
-            "auto" => Ok(Auto),
-            "first" => Ok(First),
-            "last" => Ok(Last),
-            _ => Err(NoneFound)
+        let flag = |t: AttributeType| match attr.parse_meta() {
+            Ok(Meta::Path(_)) => Ok(t),
+            _ => Err(Malformed(attr.tokens.span(), format!("`#[{}]` does not accept any arguments.", path)))
         };
+        match path.to_string().as_ref() {
+            "default" => match attr.parse_meta() {
+                Ok(Meta::Path(_)) => Ok(DefaultVariant),
+                Ok(Meta::NameValue(_)) => Err(Malformed(attr.tokens.span(), DEFAULT_SYNTAX.to_string())),
+                _ if is_name_value(&attr.tokens) => Err(Malformed(attr.tokens.span(), DEFAULT_SYNTAX.to_string())),
+                _ => Ok(Default(attr.tokens.clone()))
+            },
+            "auto" => flag(Auto),
+            "first" => flag(First),
+            "last" => flag(Last),
+            _ => Err(NoneFound)
+        }
     }
 }
 
+const DEFAULT_SYNTAX: &str = "Expected a list of values, e.g. `#[default(1)]`, or a struct literal, e.g. `#[default { x: 1 }]`.";
+
+/// Determines whether the given attribute tokens are of the name-value form,
+/// e.g. the `= 1` in `#[default = 1]`.
+fn is_name_value(tokens: &TokenStream2) -> bool {
+    matches!(tokens.clone().into_iter().next(), Some(TokenTree::Punct(ref p)) if p.as_char() == '=')
+}
+
 /// The list of errors which the macro is capable of handling when parsing
 /// attributes, currently supporting poor path syntax, missing values for
-/// `default` types, and simply `NoneFound`.
+/// `default` types, attributes written in the wrong shape, and simply
+/// `NoneFound`.
 enum AttributeParseError {
     InvalidPath(Span),
     MissingDefault(Span),
+    Malformed(Span, String),
     NoneFound
 }
 
@@ -301,11 +1603,215 @@ impl AttributeParseError {
         match *self {
             InvalidPath(s) => error(&s, "Invalid path syntax."),
             MissingDefault(s) => error(&s, "Missing argument."),
+            Malformed(s, ref msg) => error(&s, msg),
             NoneFound => error(&d, "Default values must be defined for non-unit types.")
         }
     }
 }
 
+/// Produces an implementation of `Hash` which hashes only the index of each
+/// state, provided the enum is annotated with `#[hash]`.
+fn get_hash_impl(ast: &DeriveInput) -> TokenStream2 {
+    if !has_flag(&ast.attrs, "hash") {
+        return TokenStream2::new();
+    }
+    let trait_path = get_trait_path(ast);
+    let name = &ast.ident;
+
+    quote! {
+        impl ::core::hash::Hash for #name {
+            fn hash<H: ::core::hash::Hasher>(&self, state: &mut H) {
+                ::core::hash::Hash::hash(&#trait_path::index(self), state)
+            }
+        }
+    }
+}
+
+/// Produces an implementation of `AsRef<str>` which yields the name of each
+/// state, provided the enum is annotated with `#[as_ref_str]`.
+fn get_as_ref_str_impl(ast: &DeriveInput) -> TokenStream2 {
+    if !has_flag(&ast.attrs, "as_ref_str") {
+        return TokenStream2::new();
+    }
+    let trait_path = get_trait_path(ast);
+    let name = &ast.ident;
+
+    quote! {
+        impl ::core::convert::AsRef<str> for #name {
+            fn as_ref(&self) -> &str {
+                #trait_path::name(self)
+            }
+        }
+    }
+}
+
+/// Produces an implementation of `Default` which yields the enum's default
+/// state, provided the enum is annotated with `#[std_default]`.
+fn get_std_default_impl(ast: &DeriveInput) -> TokenStream2 {
+    if !has_flag(&ast.attrs, "std_default") {
+        return TokenStream2::new();
+    }
+    let trait_path = get_trait_path(ast);
+    let name = &ast.ident;
+
+    quote! {
+        impl ::core::default::Default for #name {
+            fn default() -> Self {
+                <Self as #trait_path>::_DEFAULT
+            }
+        }
+    }
+}
+
+/// Produces inherent conversions to and from `u8` based on each state's
+/// index, provided the enum is annotated with `#[repr_u8]`.
+fn get_repr_u8_impl(ast: &DeriveInput, e: &DataEnum) -> TokenStream2 {
+    if !has_flag(&ast.attrs, "repr_u8") {
+        return TokenStream2::new();
+    }
+    let trait_path = get_trait_path(ast);
+    let allow = get_allow_deprecated(ast, e);
+    let name = &ast.ident;
+
+    quote! {
+        #allow
+        #[allow(dead_code)]
+        impl #name {
+            /// Converts the current state into its index as a `u8`.
+            pub fn to_u8(&self) -> u8 {
+                <Self as #trait_path>::index(self) as u8
+            }
+
+            /// Converts an index produced by `to_u8` back into its state, if
+            /// it is in range.
+            pub fn from_u8(v: u8) -> ::core::option::Option<Self> {
+                <Self as #trait_path>::from_index(v as usize)
+            }
+        }
+    }
+}
+
+/// Produces implementations of `From<#name>` for `usize` and `&'static str`,
+/// yielding each state's index and name, provided the enum is annotated with
+/// `#[into_primitives]`.
+fn get_into_primitives_impl(ast: &DeriveInput) -> TokenStream2 {
+    if !has_flag(&ast.attrs, "into_primitives") {
+        return TokenStream2::new();
+    }
+    let trait_path = get_trait_path(ast);
+    let name = &ast.ident;
+
+    quote! {
+        impl ::core::convert::From<#name> for usize {
+            fn from(state: #name) -> Self {
+                #trait_path::index(&state)
+            }
+        }
+
+        impl ::core::convert::From<#name> for &'static str {
+            fn from(state: #name) -> Self {
+                #trait_path::name(&state)
+            }
+        }
+    }
+}
+
+/// Produces implementations of `TryFrom<usize>` and `TryFrom<&str>`, along
+/// with an error type for each, provided the enum is annotated with
+/// `#[try_from]`. These types are named after the enum, e.g.
+/// `NumbersIndexError`, so that several enums may declare them in the same
+/// module, including inside of a function body.
+fn get_try_from_impl(ast: &DeriveInput) -> TokenStream2 {
+    if !has_flag(&ast.attrs, "try_from") {
+        return TokenStream2::new();
+    }
+    let trait_path = get_trait_path(ast);
+    let vis = &ast.vis;
+    let name = &ast.ident;
+    let index_error = format_ident!("{}IndexError", name);
+    let name_error = format_ident!("{}NameError", name);
+    let index_doc = format!("The error returned when converting an invalid index into `{}`.", name);
+    let name_doc = format!("The error returned when converting an unknown name into `{}`.", name);
+    let index_msg = format!("{{}} is not a valid index for {}", name);
+    let name_msg = format!("`{{}}` is not a valid name for {}", name);
+
+    quote! {
+        #[doc = #index_doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #vis struct #index_error(pub usize);
+
+        impl ::core::fmt::Display for #index_error {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                ::core::write!(f, #index_msg, self.0)
+            }
+        }
+
+        impl ::core::error::Error for #index_error {}
+
+        #[doc = #name_doc]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        #vis struct #name_error<'a>(pub &'a str);
+
+        impl<'a> ::core::fmt::Display for #name_error<'a> {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                ::core::write!(f, #name_msg, self.0)
+            }
+        }
+
+        impl<'a> ::core::error::Error for #name_error<'a> {}
+
+        impl ::core::convert::TryFrom<usize> for #name {
+            type Error = #index_error;
+
+            fn try_from(i: usize) -> ::core::result::Result<Self, Self::Error> {
+                <Self as #trait_path>::from_index(i).ok_or(#index_error(i))
+            }
+        }
+
+        impl<'a> ::core::convert::TryFrom<&'a str> for #name {
+            type Error = #name_error<'a>;
+
+            fn try_from(name: &'a str) -> ::core::result::Result<Self, Self::Error> {
+                <Self as #trait_path>::from_name(name).ok_or(#name_error(name))
+            }
+        }
+    }
+}
+
+/// Converts an identifier written in `UpperCamelCase` to `snake_case`.
+fn to_snake_case(ident: &str) -> String {
+    let mut snake = String::with_capacity(ident.len() + 4);
+    for (i, c) in ident.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            snake.push('_');
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+/// Ensures that flags such as `#[ord]` and `#[skip]` are not given any
+/// arguments, which `has_flag` would otherwise silently ignore.
+fn validate_flags(ast: &DeriveInput, e: &DataEnum) -> Result<(), TokenStream2> {
+    const FLAGS: &[&str] = &[
+        "ord", "hash", "try_from", "by_discriminant", "copy", "wrap", "clamp", "skip", "std_default", "repr_u8",
+        "into_primitives", "as_ref_str"
+    ];
+    let attrs = ast.attrs.iter().chain(e.variants.iter().flat_map(|v| &v.attrs));
+    for attr in attrs.filter(|a| !a.tokens.is_empty()) {
+        if let Some(flag) = FLAGS.iter().find(|f| attr.path.is_ident(f)) {
+            let msg = format!("`#[{}]` does not accept any arguments.", flag);
+            return Err(error(&attr.tokens.span(), &msg));
+        }
+    }
+    Ok(())
+}
+
+/// Determines whether the given attributes contain a bare flag, e.g. `#[ord]`.
+fn has_flag(attrs: &[Attribute], flag: &str) -> bool {
+    attrs.iter().any(|a| a.path.is_ident(flag) && a.tokens.is_empty())
+}
+
 fn error(span: &Span, msg: &str) -> TokenStream2 {
     quote_spanned! {
         *span => compile_error!(#msg);
@@ -313,10 +1819,13 @@ fn error(span: &Span, msg: &str) -> TokenStream2 {
 }
 
 /// Reports the entire stream of tokens to the user, provided the library is
-/// compiled with the `debug` feature enabled.
-fn debug(tokens: TokenStream2) -> TokenStream2 {
-    if cfg!(feature = "debug") {
-        println!("Generated tokens: \n{}", tokens.to_string());
+/// compiled with the `debug` feature enabled or the `ENUM_CYCLES_DEBUG`
+/// environment variable is set. These are written to stderr so that they
+/// appear alongside any other compiler output. Cargo does not track the
+/// variable, so toggling it requires a `cargo clean -p` of the deriving crate.
+fn debug(ast: &DeriveInput, tokens: TokenStream2) -> TokenStream2 {
+    if cfg!(feature = "debug") || env::var_os("ENUM_CYCLES_DEBUG").is_some() {
+        eprintln!("note: tokens generated for `{}`:\n{}", ast.ident, tokens);
     }
     tokens
 }
\ No newline at end of file
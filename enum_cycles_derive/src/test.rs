@@ -0,0 +1,91 @@
+use crate::*;
+use syn::parse_quote;
+
+fn parse(attr: Attribute) -> Result<AttributeType, AttributeParseError> {
+    AttributeType::try_from(&attr)
+}
+
+#[test]
+fn test_parse_flags() {
+    assert!(matches!(parse(parse_quote!(#[auto])), Ok(Auto)));
+    assert!(matches!(parse(parse_quote!(#[first])), Ok(First)));
+    assert!(matches!(parse(parse_quote!(#[last])), Ok(Last)));
+}
+
+#[test]
+fn test_parse_flags_with_arguments() {
+    assert!(matches!(parse(parse_quote!(#[first(1)])), Err(Malformed(..))));
+    assert!(matches!(parse(parse_quote!(#[last = "x"])), Err(Malformed(..))));
+    assert!(matches!(parse(parse_quote!(#[auto()])), Err(Malformed(..))));
+}
+
+#[test]
+fn test_parse_default_variant() {
+    assert!(matches!(parse(parse_quote!(#[default])), Ok(DefaultVariant)));
+}
+
+#[test]
+fn test_parse_default_list() {
+    match parse(parse_quote!(#[default(Numbers::One)])) {
+        Ok(Default(tokens)) => assert_eq!(tokens.to_string(), quote!((Numbers::One)).to_string()),
+        _ => panic!("Expected a default value.")
+    }
+    assert!(matches!(parse(parse_quote!(#[default(1 + 2, Inner::last())])), Ok(Default(_))));
+}
+
+#[test]
+fn test_parse_default_struct() {
+    match parse(parse_quote!(#[default { x: 1 }])) {
+        Ok(Default(tokens)) => assert_eq!(tokens.to_string(), quote!({ x: 1 }).to_string()),
+        _ => panic!("Expected a default value.")
+    }
+}
+
+#[test]
+fn test_parse_default_name_value() {
+    assert!(matches!(parse(parse_quote!(#[default = 1])), Err(Malformed(..))));
+    assert!(matches!(parse(parse_quote!(#[default = 1 + 2])), Err(Malformed(..))));
+}
+
+#[test]
+fn test_parse_unsupported() {
+    assert!(matches!(parse(parse_quote!(#[doc = "Docs"])), Err(NoneFound)));
+    assert!(matches!(parse(parse_quote!(#[serde(rename = "x")])), Err(NoneFound)));
+    assert!(matches!(parse(parse_quote!(#[a::b])), Err(InvalidPath(_))));
+}
+
+#[test]
+fn test_parse_rename() {
+    assert_eq!(get_rename(&parse_quote!(#[rename = "Fall"] Autumn)).ok(), Some(Some("Fall".to_string())));
+    assert_eq!(get_rename(&parse_quote!(Autumn)).ok(), Some(None));
+    assert!(get_rename(&parse_quote!(#[rename(Fall)] Autumn)).is_err());
+    assert!(get_rename(&parse_quote!(#[rename = 1] Autumn)).is_err());
+    assert!(get_rename(&parse_quote!(#[rename] Autumn)).is_err());
+}
+
+#[test]
+fn test_mentions() {
+    let ident: Ident = parse_quote!(Foo);
+    assert!(mentions(quote!(Foo), &ident));
+    assert!(mentions(quote!(Box<Foo>), &ident));
+    assert!(mentions(quote!(Option<Box<Self>>), &ident));
+    assert!(!mentions(quote!(other::Foo), &ident));
+    assert!(!mentions(quote!(Box<other::Foo>), &ident));
+    assert!(mentions(quote!(Box<self::Foo>), &ident));
+    assert!(mentions(quote!(crate::Foo), &ident));
+    assert!(!mentions(quote!(crate::other::Foo), &ident));
+    assert!(!mentions(quote!(other::self::Foo), &ident));
+    assert!(!mentions(quote!(Bar), &ident));
+}
+
+#[test]
+fn test_near_miss() {
+    assert!(is_near_miss("frist", "first"));
+    assert!(is_near_miss("lst", "last"));
+    assert!(is_near_miss("defualt", "default"));
+    assert!(is_near_miss("by_discriminent", "by_discriminant"));
+    assert!(!is_near_miss("first", "first"));
+    assert!(!is_near_miss("serde", "default"));
+    assert!(!is_near_miss("strum", "auto"));
+    assert!(!is_near_miss("test", "last"));
+}